@@ -0,0 +1,372 @@
+use crate::movimientos::{fin_del_dia, Movimiento};
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, GestorProductos};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use std::collections::HashMap;
+
+// Porcentaje acumulado del valor hasta donde llegan las clases A y B
+const LIMITE_A: f64 = 80.0;
+const LIMITE_B: f64 = 95.0;
+
+#[derive(Clone, Copy)]
+pub enum CriterioAbc {
+    // Ingreso sin impuesto de las ventas
+    Ingreso,
+    // Unidades consumidas por ventas y ensambles valoradas al costo
+    Consumo,
+}
+
+pub struct ClaseAbc {
+    pub producto: String,
+    pub valor: f64,
+    pub porcentaje_acumulado: f64,
+    pub clase: char,
+}
+
+pub struct ProductoSinMovimiento {
+    pub producto: String,
+    pub existencia: f64,
+    pub valor: f64,
+    pub ultima_venta: Option<DateTime<Local>>,
+}
+
+pub struct Rotacion {
+    pub producto: String,
+    pub consumo: f64,
+    pub existencia_promedio: f64,
+    pub rotacion: f64,
+    pub dias_inventario: Option<f64>,
+}
+
+// Implementación de métodos de análisis de inventario para GestorProductos
+impl GestorProductos {
+    // Unidades consumidas en el período por ventas netas de devoluciones y por ensambles
+    pub fn consumo_en(&self, nombre: &str, desde: NaiveDate, hasta: NaiveDate) -> f64 {
+        let fin = hasta + Duration::days(1);
+        let ensamblado: f64 = self
+            .ordenes_ensamble
+            .iter()
+            .filter(|o| (desde..fin).contains(&o.fecha.date_naive()))
+            .flat_map(|o| o.consumos.iter())
+            .filter(|(componente, _)| componente == nombre)
+            .map(|(_, cantidad)| cantidad)
+            .sum();
+        self.demanda_en(nombre, desde, fin) + ensamblado
+    }
+
+    pub fn clasificacion_abc(
+        &self,
+        desde: NaiveDate,
+        hasta: NaiveDate,
+        criterio: CriterioAbc,
+    ) -> Vec<ClaseAbc> {
+        let mut valores: HashMap<String, f64> = HashMap::new();
+        match criterio {
+            CriterioAbc::Ingreso => {
+                for movimiento in self.movimientos_venta(desde, hasta) {
+                    *valores.entry(movimiento.producto).or_insert(0.0) += movimiento.ingreso;
+                }
+            }
+            CriterioAbc::Consumo => {
+                for producto in self.productos.values().filter(|p| p.componentes.is_empty()) {
+                    let consumo = self.consumo_en(&producto.nombre, desde, hasta);
+                    valores.insert(producto.nombre.clone(), consumo * producto.costo);
+                }
+            }
+        }
+
+        let mut valores: Vec<(String, f64)> = valores
+            .into_iter()
+            .filter(|(_, valor)| *valor > 0.0)
+            .collect();
+        valores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let total: f64 = valores.iter().map(|(_, valor)| valor).sum();
+
+        let mut acumulado = 0.0;
+        valores
+            .into_iter()
+            .map(|(producto, valor)| {
+                // La clase se decide con el acumulado anterior para que el primer producto sea A
+                let clase = match acumulado / total * 100.0 {
+                    p if p < LIMITE_A => 'A',
+                    p if p < LIMITE_B => 'B',
+                    _ => 'C',
+                };
+                acumulado += valor;
+                ClaseAbc {
+                    producto,
+                    valor,
+                    porcentaje_acumulado: acumulado / total * 100.0,
+                    clase,
+                }
+            })
+            .collect()
+    }
+
+    pub fn ultima_venta(&self, nombre: &str) -> Option<DateTime<Local>> {
+        self.ventas
+            .iter()
+            .filter(|v| {
+                v.lineas.iter().any(|l| {
+                    l.producto == nombre
+                        || self
+                            .productos
+                            .get(&l.producto)
+                            .is_some_and(|p| p.componentes.iter().any(|(c, _)| c == nombre))
+                })
+            })
+            .map(|v| v.fecha)
+            .max()
+    }
+
+    // Productos con existencia que no se han vendido en los últimos días indicados
+    pub fn productos_sin_movimiento(&self, dias: i64) -> Vec<ProductoSinMovimiento> {
+        let limite = Local::now() - Duration::days(dias);
+        let mut resultado: Vec<ProductoSinMovimiento> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty() && p.cantidad_disponible > 0.0)
+            .filter_map(|p| {
+                let ultima_venta = self.ultima_venta(&p.nombre);
+                if ultima_venta.is_some_and(|fecha| fecha >= limite) {
+                    return None;
+                }
+                Some(ProductoSinMovimiento {
+                    producto: p.nombre.clone(),
+                    existencia: p.cantidad_disponible,
+                    valor: p.cantidad_disponible * p.costo,
+                    ultima_venta,
+                })
+            })
+            .collect();
+        resultado.sort_by(|a, b| b.valor.total_cmp(&a.valor));
+        resultado
+    }
+
+    // Existencia promedio de cada producto con el cierre de cada día del período. Se parte
+    // de la existencia actual y se deshacen los movimientos en una sola pasada hacia atrás
+    fn existencia_promedio(&self, desde: NaiveDate, hasta: NaiveDate) -> HashMap<String, f64> {
+        let mut existencias: HashMap<&str, f64> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty())
+            .map(|p| (p.nombre.as_str(), p.cantidad_disponible))
+            .collect();
+        let mut movimientos: Vec<&Movimiento> = self.movimientos.iter().collect();
+        movimientos.sort_by_key(|m| m.fecha);
+
+        let mut sumas: HashMap<String, f64> = HashMap::new();
+        let mut dias = 0;
+        let mut fecha = hasta;
+        while fecha >= desde {
+            if let Some(cierre) = fin_del_dia(fecha) {
+                while let Some(movimiento) = movimientos.last().filter(|m| m.fecha > cierre) {
+                    if let Some(cantidad) = existencias.get_mut(movimiento.producto.as_str()) {
+                        *cantidad -= movimiento.cantidad;
+                    }
+                    movimientos.pop();
+                }
+            }
+            for (producto, cantidad) in &existencias {
+                *sumas.entry(producto.to_string()).or_insert(0.0) += cantidad.max(0.0);
+            }
+            dias += 1;
+            fecha -= Duration::days(1);
+        }
+        for suma in sumas.values_mut() {
+            *suma /= dias.max(1) as f64;
+        }
+        sumas
+    }
+
+    // Rotación como consumo del período sobre la existencia promedio, y días que esta alcanza
+    pub fn rotacion_inventario(&self, desde: NaiveDate, hasta: NaiveDate) -> Vec<Rotacion> {
+        let dias = ((hasta - desde).num_days() + 1).max(1) as f64;
+        let promedios = self.existencia_promedio(desde, hasta);
+        let mut resultado: Vec<Rotacion> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty())
+            .map(|p| {
+                let consumo = self.consumo_en(&p.nombre, desde, hasta);
+                let existencia = promedios.get(&p.nombre).copied().unwrap_or(0.0);
+                Rotacion {
+                    producto: p.nombre.clone(),
+                    consumo,
+                    existencia_promedio: existencia,
+                    rotacion: if existencia > 0.0 {
+                        consumo / existencia
+                    } else {
+                        0.0
+                    },
+                    dias_inventario: (consumo > 0.0).then(|| existencia / (consumo / dias)),
+                }
+            })
+            .collect();
+        resultado.sort_by(|a, b| a.producto.cmp(&b.producto));
+        resultado
+    }
+}
+
+pub fn menu_analisis(gestor_productos: &GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Análisis de Inventario");
+        println!("----------------------");
+        println!("1. Clasificación ABC por Ingreso");
+        println!("2. Clasificación ABC por Valor de Consumo");
+        println!("3. Productos sin Movimiento");
+        println!("4. Rotación y Días de Inventario");
+        println!("5. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            opcion @ ("1" | "2") => {
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                let criterio = if opcion == "1" {
+                    CriterioAbc::Ingreso
+                } else {
+                    CriterioAbc::Consumo
+                };
+                let clases = gestor_productos.clasificacion_abc(desde, hasta, criterio);
+                if clases.is_empty() {
+                    println!("No hay movimientos en el período");
+                }
+                for clase in &clases {
+                    println!(
+                        "{} - {} - ${:.2} ({:.1}% acumulado)",
+                        clase.clase, clase.producto, clase.valor, clase.porcentaje_acumulado
+                    );
+                }
+                for letra in ['A', 'B', 'C'] {
+                    let de_clase: Vec<_> = clases.iter().filter(|c| c.clase == letra).collect();
+                    println!(
+                        "Clase {}: {} productos, ${:.2}",
+                        letra,
+                        de_clase.len(),
+                        de_clase.iter().map(|c| c.valor).sum::<f64>()
+                    );
+                }
+            }
+            "3" => {
+                println!("Ingrese el número de días sin ventas (deje vacío para 90):");
+                let dias = leer_entrada().parse::<i64>().unwrap_or(90);
+                let productos = gestor_productos.productos_sin_movimiento(dias);
+                if productos.is_empty() {
+                    println!(
+                        "Todos los productos con existencia se vendieron en los últimos {} días",
+                        dias
+                    );
+                }
+                for producto in &productos {
+                    println!(
+                        "{} - existencia: {} - valor: ${:.2} - última venta: {}",
+                        producto.producto,
+                        producto.existencia,
+                        producto.valor,
+                        producto
+                            .ultima_venta
+                            .map_or("nunca".to_string(), |f| f.format("%Y-%m-%d").to_string())
+                    );
+                }
+                println!(
+                    "Valor total inmovilizado: ${:.2}",
+                    productos.iter().map(|p| p.valor).sum::<f64>()
+                );
+            }
+            "4" => {
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                for rotacion in gestor_productos.rotacion_inventario(desde, hasta) {
+                    println!(
+                        "{} - consumo: {} - existencia promedio: {:.2} - rotación: {:.2} - días de inventario: {}",
+                        rotacion.producto,
+                        rotacion.consumo,
+                        rotacion.existencia_promedio,
+                        rotacion.rotacion,
+                        rotacion
+                            .dias_inventario
+                            .map_or("sin consumo".to_string(), |d| format!("{:.1}", d))
+                    );
+                }
+            }
+            "5" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movimientos::TipoMovimiento;
+    use crate::ventas::{LineaVenta, Venta};
+    use crate::Producto;
+    use chrono::TimeZone;
+
+    fn momento(dia: u32, hora: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 1, dia, hora, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn rotacion_usa_la_existencia_promedio_del_periodo() {
+        let mut gestor = GestorProductos::new();
+        let producto = Producto::new("Arroz".to_string(), String::new(), 5.0, 0.0, 0.0);
+        gestor.productos.insert("Arroz".to_string(), producto);
+        // Entran 10 el día 1 y se venden los 10 el día 2, hoy la existencia es 0
+        for (dia, cantidad, tipo) in [
+            (1, 10.0, TipoMovimiento::Inicial),
+            (2, -10.0, TipoMovimiento::Venta),
+        ] {
+            gestor.movimientos.push(Movimiento {
+                fecha: momento(dia, 10),
+                producto: "Arroz".to_string(),
+                tipo,
+                cantidad,
+                costo_anterior: 0.0,
+                costo_resultante: 0.0,
+            });
+        }
+        gestor.ventas.push(Venta {
+            id: 1,
+            fecha: momento(2, 10),
+            usuario: "admin".to_string(),
+            cliente: None,
+            lineas: vec![LineaVenta {
+                producto: "Arroz".to_string(),
+                cantidad: 10.0,
+                precio_unitario: 5.0,
+                costo_unitario: 0.0,
+                descuento: None,
+                promocion: None,
+            }],
+            descuento: None,
+            total: 50.0,
+            pagos: Vec::new(),
+            sesion: None,
+        });
+
+        let desde = momento(1, 0).date_naive();
+        let hasta = momento(2, 0).date_naive();
+        let rotacion = &gestor.rotacion_inventario(desde, hasta)[0];
+        // Cierres de 10 y 0: promedio 5, rotación 10 / 5 y 5 / (10 / 2 días) días de inventario
+        assert_eq!(rotacion.consumo, 10.0);
+        assert_eq!(rotacion.existencia_promedio, 5.0);
+        assert_eq!(rotacion.rotacion, 2.0);
+        assert_eq!(rotacion.dias_inventario, Some(1.0));
+    }
+}
@@ -0,0 +1,642 @@
+use crate::cli::producto_json;
+use crate::pagos::MetodoPago;
+use crate::persistencia::ARCHIVO_INVENTARIO;
+use crate::{generar_sku, GestorProductos, GestorUsuarios, Producto};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+// Descripción OpenAPI de todos los recursos, se sirve en /api/openapi.json
+const OPENAPI: &str = include_str!("openapi.json");
+
+const POR_PAGINA_DEFECTO: usize = 50;
+const POR_PAGINA_MAXIMO: usize = 200;
+const HORAS_SESION: i64 = 8;
+
+// El rol no se guarda en la sesión, se consulta en cada solicitud por si cambió
+struct Sesion {
+    usuario: String,
+    expira: DateTime<Local>,
+}
+
+struct Respuesta {
+    estado: u16,
+    cuerpo: Option<Value>,
+}
+
+impl Respuesta {
+    fn ok(cuerpo: Value) -> Respuesta {
+        Respuesta {
+            estado: 200,
+            cuerpo: Some(cuerpo),
+        }
+    }
+
+    fn creado(cuerpo: Value) -> Respuesta {
+        Respuesta {
+            estado: 201,
+            cuerpo: Some(cuerpo),
+        }
+    }
+
+    fn sin_contenido() -> Respuesta {
+        Respuesta {
+            estado: 204,
+            cuerpo: None,
+        }
+    }
+
+    fn error(estado: u16, mensaje: &str) -> Respuesta {
+        Respuesta {
+            estado,
+            cuerpo: Some(json!({ "error": mensaje })),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Credenciales {
+    email: String,
+    contrasena: String,
+}
+
+#[derive(Deserialize)]
+struct NuevoProducto {
+    nombre: String,
+    precio: f64,
+    #[serde(default)]
+    descripcion: String,
+    #[serde(default)]
+    cantidad: f64,
+    cantidad_minima: Option<f64>,
+    #[serde(default)]
+    costo: f64,
+    categoria: Option<String>,
+    sku: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NuevaCompra {
+    producto: String,
+    cantidad: f64,
+    costo_unitario: Option<f64>,
+    #[serde(default)]
+    proveedor: String,
+}
+
+#[derive(Deserialize)]
+struct LineaNuevaVenta {
+    producto: String,
+    cantidad: f64,
+}
+
+#[derive(Deserialize)]
+struct NuevaVenta {
+    lineas: Vec<LineaNuevaVenta>,
+    cliente: Option<String>,
+    metodo_pago: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NuevoUsuario {
+    nombre: String,
+    email: String,
+    contrasena: String,
+    #[serde(default)]
+    es_administrador: bool,
+}
+
+// Decodifica los %XX de una URL; en la consulta además el + es un espacio
+fn decodificar_url(texto: &str, consulta: bool) -> String {
+    let bytes = texto.as_bytes();
+    let mut resultado = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                match std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(byte) => {
+                        resultado.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => resultado.push(b'%'),
+                }
+            }
+            b'+' if consulta => resultado.push(b' '),
+            byte => resultado.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&resultado).into_owned()
+}
+
+fn leer_consulta(consulta: &str) -> HashMap<String, String> {
+    consulta
+        .split('&')
+        .filter(|par| !par.is_empty())
+        .map(|par| {
+            let (clave, valor) = par.split_once('=').unwrap_or((par, ""));
+            (decodificar_url(clave, true), decodificar_url(valor, true))
+        })
+        .collect()
+}
+
+// Token de 128 bits del generador aleatorio del sistema operativo
+fn generar_token() -> Result<String, Respuesta> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|err| Respuesta::error(500, &format!("No se pudo generar el token: {}", err)))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+fn leer_cuerpo<T: for<'de> Deserialize<'de>>(cuerpo: &str) -> Result<T, Respuesta> {
+    serde_json::from_str(cuerpo)
+        .map_err(|err| Respuesta::error(400, &format!("Cuerpo no válido: {}", err)))
+}
+
+// Recorta la lista según pagina y por_pagina, y devuelve los datos con el total
+fn paginar(elementos: Vec<Value>, consulta: &HashMap<String, String>) -> Result<Value, Respuesta> {
+    let numero = |clave: &str, defecto: usize| match consulta.get(clave) {
+        None => Ok(defecto),
+        Some(valor) => valor
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| Respuesta::error(400, &format!("{} no válido", clave))),
+    };
+    let pagina = numero("pagina", 1)?;
+    let por_pagina = numero("por_pagina", POR_PAGINA_DEFECTO)?.min(POR_PAGINA_MAXIMO);
+    let total = elementos.len();
+    let datos: Vec<Value> = elementos
+        .into_iter()
+        .skip((pagina - 1) * por_pagina)
+        .take(por_pagina)
+        .collect();
+    Ok(json!({
+        "datos": datos,
+        "pagina": pagina,
+        "por_pagina": por_pagina,
+        "total": total,
+    }))
+}
+
+fn fecha_consulta(
+    consulta: &HashMap<String, String>,
+    clave: &str,
+) -> Result<Option<NaiveDate>, Respuesta> {
+    match consulta.get(clave) {
+        None => Ok(None),
+        Some(valor) => NaiveDate::parse_from_str(valor, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| {
+                Respuesta::error(400, &format!("{} debe tener el formato AAAA-MM-DD", clave))
+            }),
+    }
+}
+
+struct Api<'a> {
+    gestor_productos: &'a mut GestorProductos,
+    gestor_usuarios: &'a GestorUsuarios,
+    sesiones: HashMap<String, Sesion>,
+}
+
+impl Api<'_> {
+    fn guardar(&self) -> Result<(), Respuesta> {
+        self.gestor_productos
+            .guardar(ARCHIVO_INVENTARIO)
+            .map_err(|err| {
+                Respuesta::error(500, &format!("Error al guardar el inventario: {}", err))
+            })
+    }
+
+    fn sesion(&mut self, token: Option<&str>) -> Result<&Sesion, Respuesta> {
+        let ahora = Local::now();
+        self.sesiones.retain(|_, sesion| sesion.expira > ahora);
+        token
+            .and_then(|token| self.sesiones.get(token))
+            .ok_or_else(|| Respuesta::error(401, "Token ausente, no válido o vencido"))
+    }
+
+    fn iniciar_sesion(&mut self, cuerpo: &str) -> Result<Respuesta, Respuesta> {
+        let credenciales: Credenciales = leer_cuerpo(cuerpo)?;
+        if !self
+            .gestor_usuarios
+            .autenticar_usuario(&credenciales.email, &credenciales.contrasena)
+            .unwrap_or(false)
+        {
+            return Err(Respuesta::error(
+                401,
+                "Correo electrónico o contraseña incorrectos",
+            ));
+        }
+        let token = generar_token()?;
+        let expira = Local::now() + Duration::hours(HORAS_SESION);
+        let es_administrador = self.gestor_usuarios.es_administrador(&credenciales.email);
+        self.sesiones.insert(
+            token.clone(),
+            Sesion {
+                usuario: credenciales.email.clone(),
+                expira,
+            },
+        );
+        Ok(Respuesta::creado(json!({
+            "token": token,
+            "usuario": credenciales.email,
+            "es_administrador": es_administrador,
+            "expira": expira.to_rfc3339(),
+        })))
+    }
+
+    fn listar_productos(&self, consulta: &HashMap<String, String>) -> Result<Respuesta, Respuesta> {
+        let solo_bajos = consulta.get("bajo_minimo").is_some_and(|v| v == "true");
+        let mut productos: Vec<&Producto> = self
+            .gestor_productos
+            .productos
+            .values()
+            .filter(|p| !solo_bajos || self.gestor_productos.verificar_cantidad_minima(&p.nombre))
+            .collect();
+        productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        let productos = productos
+            .into_iter()
+            .map(|p| producto_json(self.gestor_productos, p))
+            .collect();
+        Ok(Respuesta::ok(paginar(productos, consulta)?))
+    }
+
+    fn obtener_producto(&self, entrada: &str) -> Result<Respuesta, Respuesta> {
+        self.gestor_productos
+            .resolver_producto(entrada)
+            .and_then(|nombre| self.gestor_productos.buscar_producto(&nombre))
+            .map(|p| Respuesta::ok(producto_json(self.gestor_productos, p)))
+            .ok_or_else(|| Respuesta::error(404, "Producto no encontrado"))
+    }
+
+    fn crear_producto(&mut self, cuerpo: &str) -> Result<Respuesta, Respuesta> {
+        let nuevo: NuevoProducto = leer_cuerpo(cuerpo)?;
+        let nombre = nuevo.nombre.trim().to_string();
+        if nombre.is_empty() {
+            return Err(Respuesta::error(400, "El nombre no puede estar vacío"));
+        }
+        let numeros = [
+            Some(nuevo.precio),
+            Some(nuevo.cantidad),
+            nuevo.cantidad_minima,
+            Some(nuevo.costo),
+        ];
+        if numeros.into_iter().flatten().any(|n| n < 0.0) {
+            return Err(Respuesta::error(
+                400,
+                "Los valores numéricos no pueden ser negativos",
+            ));
+        }
+        if self.gestor_productos.productos.contains_key(&nombre) {
+            return Err(Respuesta::error(409, "El producto ya existe"));
+        }
+        let categoria = match &nuevo.categoria {
+            Some(ruta) => Some(
+                self.gestor_productos
+                    .buscar_categoria(ruta)
+                    .ok_or_else(|| Respuesta::error(404, "Categoría no encontrada"))?,
+            ),
+            None => None,
+        };
+        let cantidad_minima = nuevo
+            .cantidad_minima
+            .or_else(|| categoria.and_then(|c| self.gestor_productos.cantidad_minima_defecto(c)))
+            .unwrap_or(0.0);
+
+        let mut producto = Producto::new(
+            nombre.clone(),
+            nuevo.descripcion,
+            nuevo.precio,
+            nuevo.cantidad,
+            cantidad_minima,
+        );
+        producto.categoria = categoria;
+        producto.costo = nuevo.costo;
+        if let Some(sku) = &nuevo.sku {
+            producto.sku = generar_sku(sku);
+        }
+        self.gestor_productos
+            .agregar_producto(nombre.clone(), producto);
+        self.guardar()?;
+        let producto = &self.gestor_productos.productos[&nombre];
+        Ok(Respuesta::creado(producto_json(
+            self.gestor_productos,
+            producto,
+        )))
+    }
+
+    fn listar_compras(&self, consulta: &HashMap<String, String>) -> Result<Respuesta, Respuesta> {
+        let compras = self
+            .gestor_productos
+            .compras
+            .iter()
+            .rev()
+            .filter_map(|c| serde_json::to_value(c).ok())
+            .collect();
+        Ok(Respuesta::ok(paginar(compras, consulta)?))
+    }
+
+    fn crear_compra(&mut self, cuerpo: &str, usuario: &str) -> Result<Respuesta, Respuesta> {
+        let compra: NuevaCompra = leer_cuerpo(cuerpo)?;
+        let nombre = self
+            .gestor_productos
+            .resolver_producto(&compra.producto)
+            .ok_or_else(|| Respuesta::error(404, "Producto no encontrado"))?;
+        let id = self
+            .gestor_productos
+            .registrar_compra(
+                &nombre,
+                compra.cantidad,
+                compra.costo_unitario,
+                &compra.proveedor,
+                usuario,
+            )
+            .map_err(|err| Respuesta::error(422, &err))?;
+        self.guardar()?;
+        let compra = self.gestor_productos.compras.iter().find(|c| c.id == id);
+        Ok(Respuesta::creado(json!(compra)))
+    }
+
+    fn listar_ventas(&self, consulta: &HashMap<String, String>) -> Result<Respuesta, Respuesta> {
+        let desde = fecha_consulta(consulta, "desde")?;
+        let hasta = fecha_consulta(consulta, "hasta")?;
+        let ventas = self
+            .gestor_productos
+            .ventas
+            .iter()
+            .rev()
+            .filter(|v| desde.is_none_or(|d| v.fecha.date_naive() >= d))
+            .filter(|v| hasta.is_none_or(|h| v.fecha.date_naive() <= h))
+            .filter_map(|v| serde_json::to_value(v).ok())
+            .collect();
+        Ok(Respuesta::ok(paginar(ventas, consulta)?))
+    }
+
+    fn obtener_venta(&self, id: &str) -> Result<Respuesta, Respuesta> {
+        id.parse::<u32>()
+            .ok()
+            .and_then(|id| self.gestor_productos.buscar_venta(id))
+            .map(|venta| Respuesta::ok(json!(venta)))
+            .ok_or_else(|| Respuesta::error(404, "Venta no encontrada"))
+    }
+
+    fn crear_venta(&mut self, cuerpo: &str, usuario: &str) -> Result<Respuesta, Respuesta> {
+        let venta: NuevaVenta = leer_cuerpo(cuerpo)?;
+        let cliente = match &venta.cliente {
+            Some(cliente) => Some(
+                self.gestor_productos
+                    .buscar_cliente(cliente)
+                    .ok_or_else(|| Respuesta::error(404, "Cliente no encontrado"))?,
+            ),
+            None => None,
+        };
+        let metodo = match &venta.metodo_pago {
+            Some(nombre) => MetodoPago::interpretar(nombre)
+                .ok_or_else(|| Respuesta::error(400, "Método de pago no válido"))?,
+            None => MetodoPago::Efectivo,
+        };
+        let mut lineas = Vec::new();
+        for linea in &venta.lineas {
+            let nombre = self
+                .gestor_productos
+                .resolver_producto(&linea.producto)
+                .ok_or_else(|| {
+                    Respuesta::error(404, &format!("Producto no encontrado: {}", linea.producto))
+                })?;
+            lineas.push(
+                self.gestor_productos
+                    .nueva_linea_venta(&nombre, linea.cantidad)
+                    .map_err(|err| Respuesta::error(400, &format!("{}: {}", nombre, err)))?,
+            );
+        }
+        let id = self
+            .gestor_productos
+            .registrar_venta_un_pago(lineas, cliente.as_deref(), metodo, usuario)
+            .map_err(|err| Respuesta::error(422, &err))?;
+        self.guardar()?;
+        Ok(Respuesta::creado(json!(self
+            .gestor_productos
+            .buscar_venta(id))))
+    }
+
+    fn listar_usuarios(&self, consulta: &HashMap<String, String>) -> Result<Respuesta, Respuesta> {
+        let usuarios = self
+            .gestor_usuarios
+            .listar_usuarios()
+            .map_err(|err| Respuesta::error(500, &err.to_string()))?
+            .into_iter()
+            .map(|(nombre, email, es_administrador)| {
+                json!({
+                    "nombre": nombre,
+                    "email": email,
+                    "es_administrador": es_administrador,
+                })
+            })
+            .collect();
+        Ok(Respuesta::ok(paginar(usuarios, consulta)?))
+    }
+
+    fn crear_usuario(&self, cuerpo: &str) -> Result<Respuesta, Respuesta> {
+        let usuario: NuevoUsuario = leer_cuerpo(cuerpo)?;
+        if usuario.email.trim().is_empty() || usuario.contrasena.is_empty() {
+            return Err(Respuesta::error(
+                400,
+                "El correo y la contraseña son obligatorios",
+            ));
+        }
+        let existe = self
+            .gestor_usuarios
+            .existe_usuario(&usuario.email)
+            .map_err(|err| Respuesta::error(500, &err.to_string()))?;
+        if existe {
+            return Err(Respuesta::error(409, "Ya existe un usuario con ese correo"));
+        }
+        self.gestor_usuarios
+            .crear_usuario(
+                &usuario.nombre,
+                &usuario.email,
+                &usuario.contrasena,
+                usuario.es_administrador as i32,
+            )
+            .map_err(|err| Respuesta::error(500, &err.to_string()))?;
+        Ok(Respuesta::creado(json!({
+            "nombre": usuario.nombre,
+            "email": usuario.email,
+            "es_administrador": usuario.es_administrador,
+        })))
+    }
+
+    fn eliminar_usuario(&mut self, email: &str, usuario: &str) -> Result<Respuesta, Respuesta> {
+        if email == usuario {
+            return Err(Respuesta::error(409, "No puede eliminar su propio usuario"));
+        }
+        let existe = self
+            .gestor_usuarios
+            .existe_usuario(email)
+            .map_err(|err| Respuesta::error(500, &err.to_string()))?;
+        if !existe {
+            return Err(Respuesta::error(404, "Usuario no encontrado"));
+        }
+        self.gestor_usuarios
+            .eliminar_usuario(email)
+            .map_err(|err| Respuesta::error(500, &err.to_string()))?;
+        self.sesiones.retain(|_, sesion| sesion.usuario != email);
+        Ok(Respuesta::sin_contenido())
+    }
+
+    fn atender(
+        &mut self,
+        metodo: &Method,
+        ruta: &[String],
+        consulta: &HashMap<String, String>,
+        cuerpo: &str,
+        token: Option<&str>,
+    ) -> Result<Respuesta, Respuesta> {
+        let ruta: Vec<&str> = ruta.iter().map(String::as_str).collect();
+        // Rutas que no necesitan token
+        match (metodo, ruta.as_slice()) {
+            (Method::Get, ["openapi.json"]) => {
+                let descripcion: Value = serde_json::from_str(OPENAPI)
+                    .map_err(|err| Respuesta::error(500, &err.to_string()))?;
+                return Ok(Respuesta::ok(descripcion));
+            }
+            (Method::Post, ["sesiones"]) => return self.iniciar_sesion(cuerpo),
+            _ => {}
+        }
+
+        let usuario = self.sesion(token)?.usuario.clone();
+        // Un usuario eliminado pierde el acceso aunque su token no haya vencido
+        let existe = self
+            .gestor_usuarios
+            .existe_usuario(&usuario)
+            .map_err(|err| Respuesta::error(500, &err.to_string()))?;
+        if !existe {
+            self.sesiones.retain(|_, sesion| sesion.usuario != usuario);
+            return Err(Respuesta::error(
+                401,
+                "El usuario de la sesión ya no existe",
+            ));
+        }
+        let es_administrador = self.gestor_usuarios.es_administrador(&usuario);
+        // Para mantener las mismas reglas del menú, solo los administradores gestionan usuarios
+        if ruta.first() == Some(&"usuarios") && !es_administrador {
+            return Err(Respuesta::error(
+                403,
+                "Solo los administradores pueden gestionar usuarios",
+            ));
+        }
+        let hoy = Local::now().date_naive();
+        self.gestor_productos.aplicar_cambios_programados(hoy);
+        self.gestor_productos.tomar_instantaneas_pendientes(hoy);
+
+        match (metodo, ruta.as_slice()) {
+            (Method::Delete, ["sesiones"]) => {
+                if let Some(token) = token {
+                    self.sesiones.remove(token);
+                }
+                Ok(Respuesta::sin_contenido())
+            }
+            (Method::Get, ["productos"]) => self.listar_productos(consulta),
+            (Method::Post, ["productos"]) => self.crear_producto(cuerpo),
+            (Method::Get, ["productos", producto]) => self.obtener_producto(producto),
+            (Method::Get, ["existencias"]) => {
+                let existencias = self.gestor_productos.tabla_existencias().objetos();
+                Ok(Respuesta::ok(paginar(existencias, consulta)?))
+            }
+            (Method::Get, ["compras"]) => self.listar_compras(consulta),
+            (Method::Post, ["compras"]) => self.crear_compra(cuerpo, &usuario),
+            (Method::Get, ["ventas"]) => self.listar_ventas(consulta),
+            (Method::Post, ["ventas"]) => self.crear_venta(cuerpo, &usuario),
+            (Method::Get, ["ventas", id]) => self.obtener_venta(id),
+            (Method::Get, ["usuarios"]) => self.listar_usuarios(consulta),
+            (Method::Post, ["usuarios"]) => self.crear_usuario(cuerpo),
+            (Method::Delete, ["usuarios", email]) => self.eliminar_usuario(email, &usuario),
+            (
+                _,
+                ["sesiones"]
+                | ["productos"]
+                | ["productos", _]
+                | ["existencias"]
+                | ["compras"]
+                | ["ventas"]
+                | ["ventas", _]
+                | ["usuarios"]
+                | ["usuarios", _],
+            ) => Err(Respuesta::error(405, "Método no permitido")),
+            _ => Err(Respuesta::error(404, "Recurso no encontrado")),
+        }
+    }
+
+    fn atender_solicitud(&mut self, solicitud: &mut Request) -> Respuesta {
+        let url = solicitud.url().to_string();
+        let (ruta, consulta) = url.split_once('?').unwrap_or((&url, ""));
+        let Some(ruta) = ruta.strip_prefix("/api") else {
+            return Respuesta::error(404, "Recurso no encontrado");
+        };
+        let ruta: Vec<String> = ruta
+            .split('/')
+            .filter(|parte| !parte.is_empty())
+            .map(|parte| decodificar_url(parte, false))
+            .collect();
+        let consulta = leer_consulta(consulta);
+
+        let mut cuerpo = String::new();
+        if solicitud.as_reader().read_to_string(&mut cuerpo).is_err() {
+            return Respuesta::error(400, "El cuerpo debe estar en UTF-8");
+        }
+        let token = solicitud
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Authorization"))
+            .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+            .map(|token| token.trim().to_string());
+
+        let metodo = solicitud.method().clone();
+        self.atender(&metodo, &ruta, &consulta, &cuerpo, token.as_deref())
+            .unwrap_or_else(|respuesta| respuesta)
+    }
+}
+
+// Atiende las solicitudes una por una hasta que se detenga el proceso
+pub fn iniciar_servidor(
+    direccion: &str,
+    gestor_productos: &mut GestorProductos,
+    gestor_usuarios: &GestorUsuarios,
+) -> Result<(), String> {
+    let servidor = Server::http(direccion).map_err(|err| err.to_string())?;
+    println!("API disponible en http://{}/api", direccion);
+    println!(
+        "Descripción OpenAPI en http://{}/api/openapi.json",
+        direccion
+    );
+
+    let mut api = Api {
+        gestor_productos,
+        gestor_usuarios,
+        sesiones: HashMap::new(),
+    };
+    let tipo_json = Header::from_bytes("Content-Type", "application/json; charset=utf-8")
+        .map_err(|_| "Encabezado no válido".to_string())?;
+    for mut solicitud in servidor.incoming_requests() {
+        let respuesta = api.atender_solicitud(&mut solicitud);
+        let cuerpo = respuesta
+            .cuerpo
+            .map(|cuerpo| serde_json::to_string(&cuerpo).unwrap_or_default())
+            .unwrap_or_default();
+        let respuesta = Response::from_string(cuerpo)
+            .with_status_code(respuesta.estado)
+            .with_header(tipo_json.clone());
+        if let Err(err) = solicitud.respond(respuesta) {
+            eprintln!("Error al responder: {}", err);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use crate::pagos::{MetodoPago, TOLERANCIA};
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, GestorProductos};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct CierreCaja {
+    pub fecha: DateTime<Local>,
+    pub esperado: f64,
+    pub contado: f64,
+}
+
+impl CierreCaja {
+    // Positivo si sobra efectivo, negativo si falta
+    pub fn diferencia(&self) -> f64 {
+        self.contado - self.esperado
+    }
+}
+
+// Turno de caja abierto por un usuario con un fondo inicial de efectivo
+#[derive(Serialize, Deserialize)]
+pub struct SesionCaja {
+    pub id: u32,
+    pub usuario: String,
+    pub apertura: DateTime<Local>,
+    pub fondo_inicial: f64,
+    pub cierre: Option<CierreCaja>,
+}
+
+// Implementación de métodos de caja para GestorProductos
+impl GestorProductos {
+    pub fn abrir_caja(&mut self, usuario: &str, fondo_inicial: f64) -> Result<u32, String> {
+        if fondo_inicial < 0.0 {
+            return Err("El fondo inicial no puede ser negativo".to_string());
+        }
+        if self.sesion_abierta(usuario).is_some() {
+            return Err("Ya tiene una caja abierta".to_string());
+        }
+        let id = self.sesiones_caja.len() as u32 + 1;
+        self.sesiones_caja.push(SesionCaja {
+            id,
+            usuario: usuario.to_string(),
+            apertura: Local::now(),
+            fondo_inicial,
+            cierre: None,
+        });
+        Ok(id)
+    }
+
+    pub fn sesion_abierta(&self, usuario: &str) -> Option<u32> {
+        self.sesiones_caja
+            .iter()
+            .find(|s| s.usuario == usuario && s.cierre.is_none())
+            .map(|s| s.id)
+    }
+
+    pub fn buscar_sesion(&self, id: u32) -> Option<&SesionCaja> {
+        self.sesiones_caja.iter().find(|s| s.id == id)
+    }
+
+    // Fondo inicial más el efectivo cobrado en las ventas de la sesión menos los reembolsos
+    pub fn efectivo_esperado(&self, id: u32) -> f64 {
+        let fondo = self.buscar_sesion(id).map_or(0.0, |s| s.fondo_inicial);
+        let cobrado: f64 = self
+            .ventas
+            .iter()
+            .filter(|v| v.sesion == Some(id))
+            .flat_map(|v| v.pagos.iter())
+            .filter(|p| p.metodo == MetodoPago::Efectivo)
+            .map(|p| p.monto)
+            .sum();
+        let reembolsado: f64 = self
+            .devoluciones
+            .iter()
+            .filter(|d| d.sesion == Some(id) && d.metodo == MetodoPago::Efectivo)
+            .map(|d| d.reembolso)
+            .sum();
+        fondo + cobrado - reembolsado
+    }
+
+    pub fn cerrar_caja(&mut self, usuario: &str, contado: f64) -> Result<u32, String> {
+        if contado < 0.0 {
+            return Err("El efectivo contado no puede ser negativo".to_string());
+        }
+        let id = self
+            .sesion_abierta(usuario)
+            .ok_or_else(|| "No tiene una caja abierta".to_string())?;
+        let esperado = self.efectivo_esperado(id);
+        if let Some(sesion) = self.sesiones_caja.iter_mut().find(|s| s.id == id) {
+            sesion.cierre = Some(CierreCaja {
+                fecha: Local::now(),
+                esperado,
+                contado,
+            });
+        }
+        Ok(id)
+    }
+
+    pub fn mostrar_reporte_sesion(&self, id: u32) {
+        let Some(sesion) = self.buscar_sesion(id) else {
+            println!("Sesión no encontrada");
+            return;
+        };
+        let ventas: Vec<_> = self
+            .ventas
+            .iter()
+            .filter(|v| v.sesion == Some(id))
+            .collect();
+
+        println!("Sesión de caja #{} - {}", sesion.id, sesion.usuario);
+        println!("Apertura: {}", sesion.apertura.format("%Y-%m-%d %H:%M"));
+        println!("Fondo inicial: ${:.2}", sesion.fondo_inicial);
+        println!("Ventas: {}", ventas.len());
+        println!(
+            "Total vendido: ${:.2}",
+            ventas.iter().map(|v| v.total).sum::<f64>()
+        );
+        for metodo in MetodoPago::TODOS {
+            let monto: f64 = ventas
+                .iter()
+                .flat_map(|v| v.pagos.iter())
+                .filter(|p| p.metodo == metodo)
+                .map(|p| p.monto)
+                .sum();
+            println!("  {}: ${:.2}", metodo.nombre(), monto);
+        }
+        let reembolsos: f64 = self
+            .devoluciones
+            .iter()
+            .filter(|d| d.sesion == Some(id))
+            .map(|d| d.reembolso)
+            .sum();
+        println!("Reembolsos por devoluciones: ${:.2}", reembolsos);
+        match &sesion.cierre {
+            Some(cierre) => {
+                println!("Cierre: {}", cierre.fecha.format("%Y-%m-%d %H:%M"));
+                println!("Efectivo esperado: ${:.2}", cierre.esperado);
+                println!("Efectivo contado: ${:.2}", cierre.contado);
+                match cierre.diferencia() {
+                    d if d > TOLERANCIA => println!("Sobrante: ${:.2}", d),
+                    d if d < -TOLERANCIA => println!("Faltante: ${:.2}", -d),
+                    _ => println!("La caja cuadra"),
+                }
+            }
+            None => println!(
+                "Efectivo esperado en caja: ${:.2}",
+                self.efectivo_esperado(id)
+            ),
+        }
+    }
+}
+
+pub fn menu_caja(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Caja y Pagos");
+        println!("------------");
+        match gestor_productos.sesion_abierta(usuario_actual) {
+            Some(id) => println!("Caja abierta: sesión #{}", id),
+            None => println!("No tiene una caja abierta"),
+        }
+        println!("1. Abrir Caja");
+        println!("2. Estado de la Caja");
+        println!("3. Cerrar Caja");
+        println!("4. Historial de Sesiones");
+        println!("5. Desglose de Pagos por Período");
+        println!("6. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el fondo inicial en efectivo:");
+                let resultado = match leer_entrada().parse::<f64>() {
+                    Ok(fondo) => gestor_productos.abrir_caja(usuario_actual, fondo),
+                    Err(_) => Err("Monto no válido".to_string()),
+                };
+                match resultado {
+                    Ok(id) => println!("Sesión de caja #{} abierta", id),
+                    Err(err) => println!("Error al abrir la caja: {}", err),
+                }
+            }
+            "2" => match gestor_productos.sesion_abierta(usuario_actual) {
+                Some(id) => gestor_productos.mostrar_reporte_sesion(id),
+                None => println!("No tiene una caja abierta"),
+            },
+            "3" => {
+                if gestor_productos.sesion_abierta(usuario_actual).is_none() {
+                    println!("No tiene una caja abierta");
+                    pausar();
+                    continue;
+                }
+                println!("Ingrese el efectivo contado en la caja:");
+                let resultado = match leer_entrada().parse::<f64>() {
+                    Ok(contado) => gestor_productos.cerrar_caja(usuario_actual, contado),
+                    Err(_) => Err("Monto no válido".to_string()),
+                };
+                match resultado {
+                    Ok(id) => {
+                        limpiar_consola();
+                        gestor_productos.mostrar_reporte_sesion(id);
+                    }
+                    Err(err) => println!("Error al cerrar la caja: {}", err),
+                }
+            }
+            "4" => {
+                if gestor_productos.sesiones_caja.is_empty() {
+                    println!("No hay sesiones de caja");
+                }
+                for sesion in &gestor_productos.sesiones_caja {
+                    match &sesion.cierre {
+                        Some(cierre) => println!(
+                            "#{} - {} - {} a {} - Diferencia: ${:.2}",
+                            sesion.id,
+                            sesion.usuario,
+                            sesion.apertura.format("%Y-%m-%d %H:%M"),
+                            cierre.fecha.format("%H:%M"),
+                            cierre.diferencia()
+                        ),
+                        None => println!(
+                            "#{} - {} - {} - Abierta",
+                            sesion.id,
+                            sesion.usuario,
+                            sesion.apertura.format("%Y-%m-%d %H:%M")
+                        ),
+                    }
+                }
+                println!(
+                    "Ingrese el número de sesión para ver el detalle (deje vacío para volver):"
+                );
+                if let Ok(id) = leer_entrada().parse::<u32>() {
+                    gestor_productos.mostrar_reporte_sesion(id);
+                }
+            }
+            "5" => {
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                let desglose = gestor_productos.desglose_pagos(desde, hasta);
+                for (metodo, monto) in &desglose {
+                    println!("{}: ${:.2}", metodo.nombre(), monto);
+                }
+                println!(
+                    "Total: ${:.2}",
+                    desglose.iter().map(|(_, monto)| monto).sum::<f64>()
+                );
+            }
+            "6" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos, Producto};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Categoria {
+    pub id: u32,
+    pub nombre: String,
+    pub padre: Option<u32>,
+    pub cantidad_minima_defecto: Option<f64>,
+}
+
+// Resumen de inventario de una categoría incluyendo sus subcategorías
+pub struct ReporteCategoria {
+    pub productos: usize,
+    pub unidades: f64,
+    pub valor: f64,
+    pub bajo_minimo: usize,
+}
+
+// Implementación de métodos de categorías para GestorProductos
+impl GestorProductos {
+    pub fn agregar_categoria(
+        &mut self,
+        nombre: &str,
+        padre: Option<u32>,
+        cantidad_minima_defecto: Option<f64>,
+    ) -> Result<u32, String> {
+        if nombre.is_empty() || nombre.contains('>') {
+            return Err("Nombre de categoría no válido".to_string());
+        }
+        if let Some(padre) = padre {
+            if !self.categorias.contains_key(&padre) {
+                return Err("Categoría padre no encontrada".to_string());
+            }
+        }
+        if self
+            .categorias
+            .values()
+            .any(|c| c.padre == padre && c.nombre.eq_ignore_ascii_case(nombre))
+        {
+            return Err("La categoría ya existe".to_string());
+        }
+
+        let id = self.categorias.keys().max().map_or(1, |id| id + 1);
+        self.categorias.insert(
+            id,
+            Categoria {
+                id,
+                nombre: nombre.to_string(),
+                padre,
+                cantidad_minima_defecto,
+            },
+        );
+        Ok(id)
+    }
+
+    // Busca una categoría por su ruta completa, por ejemplo "Bebidas > Gaseosas"
+    pub fn buscar_categoria(&self, ruta: &str) -> Option<u32> {
+        let mut actual = None;
+        for nombre in ruta.split('>').map(str::trim) {
+            actual = Some(
+                self.categorias
+                    .values()
+                    .find(|c| c.padre == actual && c.nombre.eq_ignore_ascii_case(nombre))?
+                    .id,
+            );
+        }
+        actual
+    }
+
+    pub fn ruta_categoria(&self, id: u32) -> String {
+        let mut nombres = Vec::new();
+        let mut actual = self.categorias.get(&id);
+        while let Some(categoria) = actual {
+            nombres.push(categoria.nombre.as_str());
+            actual = categoria
+                .padre
+                .and_then(|padre| self.categorias.get(&padre));
+        }
+        nombres.reverse();
+        nombres.join(" > ")
+    }
+
+    // Devuelve la categoría indicada junto con todas sus subcategorías
+    pub fn subcategorias(&self, id: u32) -> Vec<u32> {
+        let mut resultado = vec![id];
+        let mut i = 0;
+        while i < resultado.len() {
+            let actual = resultado[i];
+            let mut hijas: Vec<u32> = self
+                .categorias
+                .values()
+                .filter(|c| c.padre == Some(actual))
+                .map(|c| c.id)
+                .collect();
+            hijas.sort();
+            resultado.extend(hijas);
+            i += 1;
+        }
+        resultado
+    }
+
+    pub fn asignar_categoria(
+        &mut self,
+        nombre: &str,
+        categoria: Option<u32>,
+    ) -> Result<(), String> {
+        if let Some(id) = categoria {
+            if !self.categorias.contains_key(&id) {
+                return Err("Categoría no encontrada".to_string());
+            }
+        }
+        if let Some(producto) = self.productos.get_mut(nombre) {
+            producto.categoria = categoria;
+            Ok(())
+        } else {
+            Err("Producto no encontrado".to_string())
+        }
+    }
+
+    pub fn productos_en_categoria(&self, id: u32) -> Vec<&Producto> {
+        let categorias = self.subcategorias(id);
+        let mut productos: Vec<&Producto> = self
+            .productos
+            .values()
+            .filter(|p| p.categoria.is_some_and(|c| categorias.contains(&c)))
+            .collect();
+        productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        productos
+    }
+
+    // La cantidad mínima por defecto se hereda de la categoría padre más cercana que la defina
+    pub fn cantidad_minima_defecto(&self, id: u32) -> Option<f64> {
+        let mut actual = self.categorias.get(&id);
+        while let Some(categoria) = actual {
+            if categoria.cantidad_minima_defecto.is_some() {
+                return categoria.cantidad_minima_defecto;
+            }
+            actual = categoria
+                .padre
+                .and_then(|padre| self.categorias.get(&padre));
+        }
+        None
+    }
+
+    pub fn reporte_categoria(&self, id: u32) -> ReporteCategoria {
+        let productos = self.productos_en_categoria(id);
+        ReporteCategoria {
+            productos: productos.len(),
+            unidades: productos.iter().map(|p| p.cantidad_disponible).sum(),
+            valor: productos
+                .iter()
+                .map(|p| p.cantidad_disponible * p.precio)
+                .sum(),
+            bajo_minimo: productos
+                .iter()
+                .filter(|p| p.cantidad_disponible <= p.cantidad_minima)
+                .count(),
+        }
+    }
+
+    fn mostrar_arbol_categorias(&self, padre: Option<u32>, nivel: usize) {
+        let mut hijas: Vec<&Categoria> = self
+            .categorias
+            .values()
+            .filter(|c| c.padre == padre)
+            .collect();
+        hijas.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        for categoria in hijas {
+            match categoria.cantidad_minima_defecto {
+                Some(minima) => println!(
+                    "{}{} (mínimo por defecto: {})",
+                    "  ".repeat(nivel),
+                    categoria.nombre,
+                    minima
+                ),
+                None => println!("{}{}", "  ".repeat(nivel), categoria.nombre),
+            }
+            self.mostrar_arbol_categorias(Some(categoria.id), nivel + 1);
+        }
+    }
+}
+
+// Pide la ruta de una categoría existente, devuelve None si no se encuentra
+pub fn pedir_categoria(gestor_productos: &GestorProductos) -> Option<u32> {
+    println!("Ingrese la categoría (por ejemplo: Bebidas > Gaseosas):");
+    let ruta = leer_entrada();
+    let categoria = gestor_productos.buscar_categoria(&ruta);
+    if categoria.is_none() {
+        println!("Categoría no encontrada");
+    }
+    categoria
+}
+
+pub fn menu_categorias(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Categorías");
+        println!("----------");
+        println!("1. Ver Árbol de Categorías");
+        println!("2. Agregar Categoría");
+        println!("3. Asignar Categoría a Producto");
+        println!("4. Listar Productos por Categoría");
+        println!("5. Reporte por Categoría");
+        println!("6. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                if gestor_productos.categorias.is_empty() {
+                    println!("No hay categorías registradas");
+                } else {
+                    gestor_productos.mostrar_arbol_categorias(None, 0);
+                }
+            }
+            "2" => {
+                println!("Ingrese el nombre de la categoría:");
+                let nombre = leer_entrada();
+
+                println!("Ingrese la categoría padre (deje vacío si es una categoría principal):");
+                let ruta_padre = leer_entrada();
+                let padre = if ruta_padre.is_empty() {
+                    None
+                } else {
+                    match gestor_productos.buscar_categoria(&ruta_padre) {
+                        Some(id) => Some(id),
+                        None => {
+                            println!("Categoría padre no encontrada");
+                            pausar();
+                            continue;
+                        }
+                    }
+                };
+
+                println!("Ingrese la cantidad mínima por defecto (deje vacío para heredarla):");
+                let minima = leer_entrada();
+                let cantidad_minima_defecto = if minima.is_empty() {
+                    None
+                } else {
+                    match minima.parse::<f64>() {
+                        Ok(minima) => Some(minima),
+                        Err(_) => {
+                            println!("Cantidad mínima no válida");
+                            pausar();
+                            continue;
+                        }
+                    }
+                };
+
+                match gestor_productos.agregar_categoria(&nombre, padre, cantidad_minima_defecto) {
+                    Ok(_) => println!("Categoría agregada exitosamente"),
+                    Err(err) => println!("Error al agregar la categoría: {}", err),
+                }
+            }
+            "3" => {
+                println!("Ingrese el nombre del producto:");
+                let nombre = leer_entrada();
+                if let Some(categoria) = pedir_categoria(gestor_productos) {
+                    match gestor_productos.asignar_categoria(&nombre, Some(categoria)) {
+                        Ok(()) => println!("Categoría asignada exitosamente"),
+                        Err(err) => println!("{}", err),
+                    }
+                }
+            }
+            "4" => {
+                if let Some(categoria) = pedir_categoria(gestor_productos) {
+                    let productos = gestor_productos.productos_en_categoria(categoria);
+                    if productos.is_empty() {
+                        println!("No hay productos en esta categoría");
+                    }
+                    for producto in productos {
+                        println!(
+                            "{} - {} - ${} - Disponible: {}",
+                            producto.nombre,
+                            gestor_productos.ruta_categoria(producto.categoria.unwrap()),
+                            producto.precio,
+                            producto.cantidad_disponible
+                        );
+                    }
+                }
+            }
+            "5" => {
+                if let Some(categoria) = pedir_categoria(gestor_productos) {
+                    let reporte = gestor_productos.reporte_categoria(categoria);
+                    println!("Categoría: {}", gestor_productos.ruta_categoria(categoria));
+                    println!("Productos: {}", reporte.productos);
+                    println!("Unidades disponibles: {}", reporte.unidades);
+                    println!("Valor del inventario: ${:.2}", reporte.valor);
+                    println!("Productos bajo el mínimo: {}", reporte.bajo_minimo);
+                }
+            }
+            "6" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
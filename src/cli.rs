@@ -0,0 +1,547 @@
+use crate::pagos::MetodoPago;
+use crate::persistencia::ARCHIVO_INVENTARIO;
+use crate::{GestorProductos, GestorUsuarios, Producto};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+// Códigos de salida para que los scripts distingan la causa de un fallo
+pub const SALIDA_EXITO: i32 = 0;
+// La operación fue rechazada, por ejemplo por falta de existencias
+pub const SALIDA_ERROR: i32 = 1;
+pub const SALIDA_USO: i32 = 2;
+pub const SALIDA_AUTENTICACION: i32 = 3;
+pub const SALIDA_PERMISO: i32 = 4;
+pub const SALIDA_NO_ENCONTRADO: i32 = 5;
+
+// Variables de entorno para no pasar la contraseña en la línea de comandos
+const VARIABLE_USUARIO: &str = "SHOP_USER";
+const VARIABLE_CONTRASENA: &str = "SHOP_PASSWORD";
+// Contraseña del usuario nuevo en user add; si no está se lee de la entrada estándar
+const VARIABLE_CONTRASENA_NUEVA: &str = "SHOP_NEW_PASSWORD";
+
+const OPCIONES_CON_VALOR: [&str; 12] = [
+    "user",
+    "password",
+    "price",
+    "quantity",
+    "minimum",
+    "description",
+    "cost",
+    "category",
+    "sku",
+    "client",
+    "payment",
+    "port",
+];
+const BANDERAS: [&str; 4] = ["json", "low-stock", "admin", "help"];
+
+const AYUDA: &str = "Uso: shop <comando> [opciones]
+
+Comandos:
+  product add <nombre> --price <precio> [--quantity <cantidad>] [--minimum <mínimo>]
+              [--description <texto>] [--cost <costo>] [--category <ruta>] [--sku <sku>]
+  product list [--low-stock]
+  product show <nombre, SKU o código de barras>
+  sell <nombre, SKU o código de barras> <cantidad> [--client <cliente>]
+       [--payment efectivo|tarjeta|transferencia|credito]
+  user add <nombre> <correo> [--admin]
+           La contraseña se toma de SHOP_NEW_PASSWORD o de la primera línea de la entrada
+  serve [--port <puerto>]  API REST en 127.0.0.1 (puerto 8080 por defecto)
+
+Opciones generales:
+  --user <correo> --password <contraseña>  Credenciales (o SHOP_USER y SHOP_PASSWORD)
+  --json                                   Salida en JSON
+  --help                                   Muestra esta ayuda
+
+Códigos de salida: 0 éxito, 1 operación rechazada, 2 uso incorrecto,
+3 credenciales incorrectas, 4 permiso denegado, 5 no encontrado";
+
+const PUERTO_API: u16 = 8080;
+
+pub struct ErrorCli {
+    pub codigo: i32,
+    pub mensaje: String,
+}
+
+impl ErrorCli {
+    fn new(codigo: i32, mensaje: impl Into<String>) -> ErrorCli {
+        ErrorCli {
+            codigo,
+            mensaje: mensaje.into(),
+        }
+    }
+}
+
+// Resultado de un comando en JSON y en texto para personas
+struct Salida {
+    json: Value,
+    texto: String,
+}
+
+struct Argumentos {
+    posicionales: Vec<String>,
+    opciones: HashMap<String, String>,
+    banderas: Vec<String>,
+}
+
+impl Argumentos {
+    fn interpretar(argumentos: &[String]) -> Result<Argumentos, ErrorCli> {
+        let mut resultado = Argumentos {
+            posicionales: Vec::new(),
+            opciones: HashMap::new(),
+            banderas: Vec::new(),
+        };
+        let mut restantes = argumentos.iter();
+        while let Some(argumento) = restantes.next() {
+            let Some(nombre) = argumento.strip_prefix("--") else {
+                resultado.posicionales.push(argumento.clone());
+                continue;
+            };
+            // Se aceptan tanto --opcion valor como --opcion=valor
+            let (nombre, valor) = match nombre.split_once('=') {
+                Some((nombre, valor)) => (nombre, Some(valor.to_string())),
+                None => (nombre, None),
+            };
+            if BANDERAS.contains(&nombre) && valor.is_none() {
+                resultado.banderas.push(nombre.to_string());
+            } else if OPCIONES_CON_VALOR.contains(&nombre) {
+                let valor = valor.or_else(|| restantes.next().cloned()).ok_or_else(|| {
+                    ErrorCli::new(SALIDA_USO, format!("Falta el valor de --{}", nombre))
+                })?;
+                resultado.opciones.insert(nombre.to_string(), valor);
+            } else {
+                return Err(ErrorCli::new(
+                    SALIDA_USO,
+                    format!("Opción desconocida: --{}", nombre),
+                ));
+            }
+        }
+        Ok(resultado)
+    }
+
+    fn bandera(&self, nombre: &str) -> bool {
+        self.banderas.iter().any(|b| b == nombre)
+    }
+
+    fn opcion(&self, nombre: &str) -> Option<&str> {
+        self.opciones.get(nombre).map(String::as_str)
+    }
+
+    fn numero(&self, nombre: &str) -> Result<Option<f64>, ErrorCli> {
+        match self.opcion(nombre) {
+            None => Ok(None),
+            Some(valor) => match valor.parse::<f64>() {
+                Ok(numero) if numero >= 0.0 => Ok(Some(numero)),
+                _ => Err(ErrorCli::new(
+                    SALIDA_USO,
+                    format!("Valor no válido para --{}: {}", nombre, valor),
+                )),
+            },
+        }
+    }
+
+    fn posicional(&self, indice: usize, nombre: &str) -> Result<&str, ErrorCli> {
+        self.posicionales
+            .get(indice)
+            .map(String::as_str)
+            .ok_or_else(|| ErrorCli::new(SALIDA_USO, format!("Falta el argumento {}", nombre)))
+    }
+}
+
+pub fn producto_json(gestor_productos: &GestorProductos, producto: &Producto) -> Value {
+    json!({
+        "nombre": producto.nombre,
+        "sku": producto.sku,
+        "descripcion": producto.descripcion,
+        "categoria": producto.categoria.map(|c| gestor_productos.ruta_categoria(c)),
+        "precio": producto.precio,
+        "costo": producto.costo,
+        "cantidad": producto.cantidad_disponible,
+        "cantidad_minima": producto.cantidad_minima,
+        "bajo_minimo": gestor_productos.verificar_cantidad_minima(&producto.nombre),
+        "codigos_barras": producto.codigos_barras,
+    })
+}
+
+fn producto_texto(producto: &Producto) -> String {
+    format!(
+        "{} ({}) - precio: ${:.2} - cantidad: {} - mínimo: {}",
+        producto.nombre,
+        producto.sku,
+        producto.precio,
+        producto.cantidad_disponible,
+        producto.cantidad_minima
+    )
+}
+
+fn guardar_inventario(gestor_productos: &GestorProductos) -> Result<(), ErrorCli> {
+    gestor_productos.guardar(ARCHIVO_INVENTARIO).map_err(|err| {
+        ErrorCli::new(
+            SALIDA_ERROR,
+            format!("Error al guardar el inventario: {}", err),
+        )
+    })
+}
+
+fn agregar_producto(
+    argumentos: &Argumentos,
+    gestor_productos: &mut GestorProductos,
+) -> Result<Salida, ErrorCli> {
+    let nombre = argumentos.posicional(2, "<nombre>")?.to_string();
+    if gestor_productos.productos.contains_key(&nombre) {
+        return Err(ErrorCli::new(
+            SALIDA_ERROR,
+            format!("El producto {} ya existe", nombre),
+        ));
+    }
+    let precio = argumentos
+        .numero("price")?
+        .ok_or_else(|| ErrorCli::new(SALIDA_USO, "Falta --price"))?;
+    let categoria = match argumentos.opcion("category") {
+        Some(ruta) => Some(gestor_productos.buscar_categoria(ruta).ok_or_else(|| {
+            ErrorCli::new(
+                SALIDA_NO_ENCONTRADO,
+                format!("Categoría no encontrada: {}", ruta),
+            )
+        })?),
+        None => None,
+    };
+    let cantidad_minima = match argumentos.numero("minimum")? {
+        Some(minimo) => minimo,
+        None => categoria
+            .and_then(|c| gestor_productos.cantidad_minima_defecto(c))
+            .unwrap_or(0.0),
+    };
+
+    let mut producto = Producto::new(
+        nombre.clone(),
+        argumentos.opcion("description").unwrap_or("").to_string(),
+        precio,
+        argumentos.numero("quantity")?.unwrap_or(0.0),
+        cantidad_minima,
+    );
+    producto.categoria = categoria;
+    producto.costo = argumentos.numero("cost")?.unwrap_or(0.0);
+    if let Some(sku) = argumentos.opcion("sku") {
+        producto.sku = crate::generar_sku(sku);
+    }
+    gestor_productos.agregar_producto(nombre.clone(), producto);
+    guardar_inventario(gestor_productos)?;
+
+    let producto = &gestor_productos.productos[&nombre];
+    Ok(Salida {
+        json: producto_json(gestor_productos, producto),
+        texto: format!("Producto agregado: {}", producto_texto(producto)),
+    })
+}
+
+fn listar_productos(argumentos: &Argumentos, gestor_productos: &GestorProductos) -> Salida {
+    let solo_bajos = argumentos.bandera("low-stock");
+    let mut productos: Vec<&Producto> = gestor_productos
+        .productos
+        .values()
+        .filter(|p| !solo_bajos || gestor_productos.verificar_cantidad_minima(&p.nombre))
+        .collect();
+    productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+    Salida {
+        json: Value::Array(
+            productos
+                .iter()
+                .map(|p| producto_json(gestor_productos, p))
+                .collect(),
+        ),
+        texto: productos
+            .iter()
+            .map(|p| producto_texto(p))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
+}
+
+fn mostrar_producto(
+    argumentos: &Argumentos,
+    gestor_productos: &GestorProductos,
+) -> Result<Salida, ErrorCli> {
+    let entrada = argumentos.posicional(2, "<producto>")?;
+    let producto = gestor_productos
+        .resolver_producto(entrada)
+        .and_then(|nombre| gestor_productos.buscar_producto(&nombre))
+        .ok_or_else(|| ErrorCli::new(SALIDA_NO_ENCONTRADO, "Producto no encontrado"))?;
+    Ok(Salida {
+        json: producto_json(gestor_productos, producto),
+        texto: producto_texto(producto),
+    })
+}
+
+// Venta de un solo producto pagada completa con un método de pago
+fn vender(
+    argumentos: &Argumentos,
+    gestor_productos: &mut GestorProductos,
+    usuario: &str,
+) -> Result<Salida, ErrorCli> {
+    let entrada = argumentos.posicional(1, "<producto>")?;
+    let cantidad = argumentos
+        .posicional(2, "<cantidad>")?
+        .parse::<f64>()
+        .map_err(|_| ErrorCli::new(SALIDA_USO, "Cantidad no válida"))?;
+    let nombre = gestor_productos
+        .resolver_producto(entrada)
+        .ok_or_else(|| ErrorCli::new(SALIDA_NO_ENCONTRADO, "Producto no encontrado"))?;
+    let cliente = match argumentos.opcion("client") {
+        Some(cliente) => Some(gestor_productos.buscar_cliente(cliente).ok_or_else(|| {
+            ErrorCli::new(
+                SALIDA_NO_ENCONTRADO,
+                format!("Cliente no encontrado: {}", cliente),
+            )
+        })?),
+        None => None,
+    };
+    let metodo = match argumentos.opcion("payment") {
+        Some(nombre) => MetodoPago::interpretar(nombre).ok_or_else(|| {
+            ErrorCli::new(SALIDA_USO, format!("Método de pago no válido: {}", nombre))
+        })?,
+        None => MetodoPago::Efectivo,
+    };
+
+    let linea = gestor_productos
+        .nueva_linea_venta(&nombre, cantidad)
+        .map_err(|err| ErrorCli::new(SALIDA_ERROR, err))?;
+    let id = gestor_productos
+        .registrar_venta_un_pago(vec![linea], cliente.as_deref(), metodo, usuario)
+        .map_err(|err| ErrorCli::new(SALIDA_ERROR, err))?;
+    let total = gestor_productos.buscar_venta(id).map_or(0.0, |v| v.total);
+    guardar_inventario(gestor_productos)?;
+
+    let bajo_minimo = gestor_productos.verificar_cantidad_minima(&nombre);
+    let mut texto = format!(
+        "Venta #{} registrada: {} x {} - total: ${:.2}",
+        id, cantidad, nombre, total
+    );
+    if bajo_minimo {
+        texto.push_str(&format!("\nAviso: {} está bajo su cantidad mínima", nombre));
+    }
+    Ok(Salida {
+        json: json!({
+            "venta": id,
+            "producto": nombre,
+            "cantidad": cantidad,
+            "total": total,
+            "metodo_pago": metodo.nombre(),
+            "bajo_minimo": bajo_minimo,
+        }),
+        texto,
+    })
+}
+
+// Fuera de la línea de comandos la contraseña no queda en el historial ni en la lista de procesos
+fn contrasena_nueva() -> Result<String, ErrorCli> {
+    let contrasena = match std::env::var(VARIABLE_CONTRASENA_NUEVA) {
+        Ok(contrasena) => contrasena,
+        Err(_) => {
+            let mut linea = String::new();
+            std::io::stdin().read_line(&mut linea).map_err(|err| {
+                ErrorCli::new(
+                    SALIDA_ERROR,
+                    format!("Error al leer la contraseña: {}", err),
+                )
+            })?;
+            linea.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    if contrasena.is_empty() {
+        return Err(ErrorCli::new(
+            SALIDA_USO,
+            format!(
+                "Falta la contraseña: use {} o escríbala en la entrada estándar",
+                VARIABLE_CONTRASENA_NUEVA
+            ),
+        ));
+    }
+    Ok(contrasena)
+}
+
+fn agregar_usuario(
+    argumentos: &Argumentos,
+    gestor_usuarios: &GestorUsuarios,
+    usuario: &str,
+) -> Result<Salida, ErrorCli> {
+    if !gestor_usuarios.es_administrador(usuario) {
+        return Err(ErrorCli::new(
+            SALIDA_PERMISO,
+            "Solo los administradores pueden agregar usuarios",
+        ));
+    }
+    let nombre = argumentos.posicional(2, "<nombre>")?;
+    let email = argumentos.posicional(3, "<correo>")?;
+    if argumentos.posicionales.len() > 4 {
+        return Err(ErrorCli::new(
+            SALIDA_USO,
+            format!(
+                "La contraseña no se pasa como argumento, use {} o la entrada estándar",
+                VARIABLE_CONTRASENA_NUEVA
+            ),
+        ));
+    }
+    let contrasena = contrasena_nueva()?;
+    let es_administrador = argumentos.bandera("admin");
+    gestor_usuarios
+        .crear_usuario(nombre, email, &contrasena, es_administrador as i32)
+        .map_err(|err| {
+            ErrorCli::new(SALIDA_ERROR, format!("Error al crear el usuario: {}", err))
+        })?;
+    Ok(Salida {
+        json: json!({
+            "nombre": nombre,
+            "email": email,
+            "es_administrador": es_administrador,
+        }),
+        texto: format!("Usuario {} creado", email),
+    })
+}
+
+// Las credenciales de las opciones tienen prioridad sobre las variables de entorno
+fn autenticar(
+    argumentos: &Argumentos,
+    gestor_usuarios: &GestorUsuarios,
+) -> Result<String, ErrorCli> {
+    let credencial = |opcion: &str, variable: &str| {
+        argumentos
+            .opcion(opcion)
+            .map(str::to_string)
+            .or_else(|| std::env::var(variable).ok())
+    };
+    let (Some(email), Some(contrasena)) = (
+        credencial("user", VARIABLE_USUARIO),
+        credencial("password", VARIABLE_CONTRASENA),
+    ) else {
+        return Err(ErrorCli::new(
+            SALIDA_AUTENTICACION,
+            format!(
+                "Faltan las credenciales: use --user y --password o {} y {}",
+                VARIABLE_USUARIO, VARIABLE_CONTRASENA
+            ),
+        ));
+    };
+    if gestor_usuarios
+        .autenticar_usuario(&email, &contrasena)
+        .unwrap_or(false)
+    {
+        Ok(email)
+    } else {
+        Err(ErrorCli::new(
+            SALIDA_AUTENTICACION,
+            "Correo electrónico o contraseña incorrectos",
+        ))
+    }
+}
+
+fn servir(
+    argumentos: &Argumentos,
+    gestor_productos: &mut GestorProductos,
+    gestor_usuarios: &GestorUsuarios,
+) -> Result<Salida, ErrorCli> {
+    let puerto = match argumentos.opcion("port") {
+        Some(puerto) => puerto
+            .parse::<u16>()
+            .map_err(|_| ErrorCli::new(SALIDA_USO, format!("Puerto no válido: {}", puerto)))?,
+        None => PUERTO_API,
+    };
+    crate::api::iniciar_servidor(
+        &format!("127.0.0.1:{}", puerto),
+        gestor_productos,
+        gestor_usuarios,
+    )
+    .map_err(|err| ErrorCli::new(SALIDA_ERROR, format!("No se pudo iniciar la API: {}", err)))?;
+    Ok(Salida {
+        json: Value::Null,
+        texto: String::new(),
+    })
+}
+
+fn ejecutar_comando(
+    argumentos: &Argumentos,
+    gestor_productos: &mut GestorProductos,
+    gestor_usuarios: &GestorUsuarios,
+) -> Result<Salida, ErrorCli> {
+    // La API autentica cada solicitud con su propio token
+    if argumentos
+        .posicionales
+        .first()
+        .is_some_and(|c| c == "serve")
+    {
+        return servir(argumentos, gestor_productos, gestor_usuarios);
+    }
+    let usuario = autenticar(argumentos, gestor_usuarios)?;
+    // Igual que al iniciar el menú interactivo. Se guarda aquí porque los comandos de
+    // consulta terminan sin guardar el inventario
+    let hoy = chrono::Local::now().date_naive();
+    let aplicados = gestor_productos.aplicar_cambios_programados(hoy);
+    let instantaneas = gestor_productos.tomar_instantaneas_pendientes(hoy);
+    if aplicados > 0 || instantaneas > 0 {
+        guardar_inventario(gestor_productos)?;
+    }
+
+    let comando: Vec<&str> = argumentos
+        .posicionales
+        .iter()
+        .take(2)
+        .map(String::as_str)
+        .collect();
+    match comando.as_slice() {
+        ["product", "add"] => agregar_producto(argumentos, gestor_productos),
+        ["product", "list"] => Ok(listar_productos(argumentos, gestor_productos)),
+        ["product", "show"] => mostrar_producto(argumentos, gestor_productos),
+        ["sell", ..] => vender(argumentos, gestor_productos, &usuario),
+        ["user", "add"] => agregar_usuario(argumentos, gestor_usuarios, &usuario),
+        _ => Err(ErrorCli::new(
+            SALIDA_USO,
+            format!(
+                "Comando desconocido: {}\n\n{}",
+                argumentos.posicionales.join(" "),
+                AYUDA
+            ),
+        )),
+    }
+}
+
+// Ejecuta un comando sin menú interactivo y devuelve el código de salida del proceso
+pub fn ejecutar(
+    argumentos: &[String],
+    gestor_productos: &mut GestorProductos,
+    gestor_usuarios: &GestorUsuarios,
+) -> i32 {
+    let argumentos = match Argumentos::interpretar(argumentos) {
+        Ok(argumentos) => argumentos,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err.mensaje, AYUDA);
+            return err.codigo;
+        }
+    };
+    if argumentos.bandera("help") || argumentos.posicionales.first().is_some_and(|c| c == "help") {
+        println!("{}", AYUDA);
+        return SALIDA_EXITO;
+    }
+
+    let json = argumentos.bandera("json");
+    match ejecutar_comando(&argumentos, gestor_productos, gestor_usuarios) {
+        Ok(salida) => {
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&salida.json).unwrap_or_default()
+                );
+            } else if !salida.texto.is_empty() {
+                println!("{}", salida.texto);
+            }
+            SALIDA_EXITO
+        }
+        Err(err) => {
+            if json {
+                println!("{}", json!({ "error": err.mensaje, "codigo": err.codigo }));
+            } else {
+                eprintln!("Error: {}", err.mensaje);
+            }
+            err.codigo
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::GestorProductos;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Cliente {
+    pub nombre: String,
+    // Lista de precios asignada, sin lista se usa la lista general
+    pub lista: Option<String>,
+}
+
+// Implementación de métodos de clientes para GestorProductos
+impl GestorProductos {
+    pub fn registrar_cliente(&mut self, nombre: &str, lista: Option<&str>) -> Result<(), String> {
+        if nombre.is_empty() {
+            return Err("El cliente debe tener un nombre".to_string());
+        }
+        if let Some(lista) = lista {
+            if !self.listas_precios.contains_key(lista) {
+                return Err("Lista de precios no encontrada".to_string());
+            }
+        }
+        // Si el cliente ya existe solo se actualiza su lista
+        self.clientes.insert(
+            nombre.to_string(),
+            Cliente {
+                nombre: nombre.to_string(),
+                lista: lista.map(str::to_string),
+            },
+        );
+        Ok(())
+    }
+
+    // Busca un cliente por su nombre sin distinguir mayúsculas
+    pub fn buscar_cliente(&self, nombre: &str) -> Option<String> {
+        self.clientes
+            .values()
+            .find(|c| c.nombre.eq_ignore_ascii_case(nombre))
+            .map(|c| c.nombre.clone())
+    }
+}
@@ -0,0 +1,431 @@
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use std::fs::File;
+use std::io::BufWriter;
+
+// Prefijo de EAN-13 reservado para uso interno de la tienda
+const PREFIJO_INTERNO: &str = "20";
+
+const EAN_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+
+// Paridad de los seis dígitos de la izquierda según el primer dígito del EAN-13
+const EAN_PARIDAD: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+// Anchos de barras y espacios de cada símbolo de Code 128
+const CODE128: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+const CODE128_INICIO_B: usize = 104;
+const CODE128_FIN: usize = 106;
+
+#[derive(Debug, PartialEq)]
+pub enum TipoCodigo {
+    Ean13,
+    UpcA,
+    Code128,
+}
+
+// Dígito verificador de EAN-13 y UPC-A, los pesos 3 y 1 se alternan desde la derecha
+pub fn digito_verificador(digitos: &str) -> u32 {
+    let suma: u32 = digitos
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d * 3 } else { d })
+        .sum();
+    (10 - suma % 10) % 10
+}
+
+pub fn validar_codigo(codigo: &str) -> Result<TipoCodigo, String> {
+    if codigo.is_empty() {
+        return Err("El código está vacío".to_string());
+    }
+    let numerico = codigo.chars().all(|c| c.is_ascii_digit());
+    if numerico && (codigo.len() == 13 || codigo.len() == 12) {
+        let (datos, verificador) = codigo.split_at(codigo.len() - 1);
+        if digito_verificador(datos).to_string() != verificador {
+            return Err("Dígito verificador incorrecto".to_string());
+        }
+        return Ok(if codigo.len() == 13 {
+            TipoCodigo::Ean13
+        } else {
+            TipoCodigo::UpcA
+        });
+    }
+    if codigo.chars().all(|c| (' '..='~').contains(&c)) {
+        Ok(TipoCodigo::Code128)
+    } else {
+        Err("El código contiene caracteres no válidos".to_string())
+    }
+}
+
+// Un UPC-A se guarda como EAN-13 con un cero al inicio, así ambos lectores encuentran el producto
+pub fn normalizar_codigo(codigo: &str) -> String {
+    if codigo.len() == 12 && codigo.chars().all(|c| c.is_ascii_digit()) {
+        format!("0{}", codigo)
+    } else {
+        codigo.to_string()
+    }
+}
+
+// Devuelve los módulos del código, true para barra y false para espacio
+pub fn modulos_codigo(codigo: &str) -> Result<Vec<bool>, String> {
+    match validar_codigo(codigo)? {
+        TipoCodigo::Ean13 | TipoCodigo::UpcA => Ok(modulos_ean13(&normalizar_codigo(codigo))),
+        TipoCodigo::Code128 => Ok(modulos_code128(codigo)),
+    }
+}
+
+fn modulos_ean13(codigo: &str) -> Vec<bool> {
+    let digitos: Vec<usize> = codigo
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as usize)
+        .collect();
+    let mut patron = String::from("101");
+    for (i, paridad) in EAN_PARIDAD[digitos[0]].chars().enumerate() {
+        let l = EAN_L[digitos[i + 1]];
+        if paridad == 'L' {
+            patron.push_str(l);
+        } else {
+            // Los códigos G son los R (complemento de L) leídos al revés
+            patron.extend(l.chars().rev().map(|c| if c == '0' { '1' } else { '0' }));
+        }
+    }
+    patron.push_str("01010");
+    for &digito in &digitos[7..] {
+        patron.extend(
+            EAN_L[digito]
+                .chars()
+                .map(|c| if c == '0' { '1' } else { '0' }),
+        );
+    }
+    patron.push_str("101");
+    patron.chars().map(|c| c == '1').collect()
+}
+
+fn modulos_code128(codigo: &str) -> Vec<bool> {
+    let mut simbolos = vec![CODE128_INICIO_B];
+    simbolos.extend(codigo.chars().map(|c| c as usize - 32));
+    let suma: usize = CODE128_INICIO_B
+        + simbolos[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s * (i + 1))
+            .sum::<usize>();
+    simbolos.push(suma % 103);
+    simbolos.push(CODE128_FIN);
+
+    let mut modulos = Vec::new();
+    for simbolo in simbolos {
+        for (i, ancho) in CODE128[simbolo].chars().enumerate() {
+            let ancho = ancho.to_digit(10).unwrap_or(1) as usize;
+            modulos.extend(std::iter::repeat_n(i % 2 == 0, ancho));
+        }
+    }
+    modulos
+}
+
+// Módulos de silencio a cada lado del código
+const MARGEN: usize = 10;
+
+pub fn generar_svg(codigo: &str) -> Result<String, String> {
+    let modulos = modulos_codigo(codigo)?;
+    let escala = 2;
+    let alto = 60;
+    let ancho = (modulos.len() + MARGEN * 2) * escala;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        ancho,
+        alto + 20
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for (i, &barra) in modulos.iter().enumerate() {
+        if barra {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"black\"/>\n",
+                (i + MARGEN) * escala,
+                escala,
+                alto
+            ));
+        }
+    }
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"14\" text-anchor=\"middle\">{}</text>\n",
+        ancho / 2,
+        alto + 16,
+        escapar_xml(codigo)
+    ));
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+pub fn escapar_xml(texto: &str) -> String {
+    texto
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn guardar_png(codigo: &str, ruta: &str) -> Result<(), String> {
+    let modulos = modulos_codigo(codigo)?;
+    let escala = 2;
+    let alto = 60;
+    let ancho = (modulos.len() + MARGEN * 2) * escala;
+
+    let mut fila = vec![255u8; ancho];
+    for (i, &barra) in modulos.iter().enumerate() {
+        if barra {
+            let inicio = (i + MARGEN) * escala;
+            fila[inicio..inicio + escala].fill(0);
+        }
+    }
+    let pixeles = fila.repeat(alto);
+
+    let archivo = File::create(ruta).map_err(|err| err.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(archivo), ancho as u32, alto as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer
+        .write_image_data(&pixeles)
+        .map_err(|err| err.to_string())
+}
+
+// Implementación de métodos de códigos de barras para GestorProductos
+impl GestorProductos {
+    pub fn agregar_codigo_barras(&mut self, nombre: &str, codigo: &str) -> Result<(), String> {
+        validar_codigo(codigo)?;
+        let codigo = normalizar_codigo(codigo);
+        if let Some(otro) = self
+            .productos
+            .values()
+            .find(|p| p.codigos_barras.contains(&codigo))
+        {
+            return Err(format!("El código ya pertenece a {}", otro.nombre));
+        }
+        match self.productos.get_mut(nombre) {
+            Some(producto) => {
+                producto.codigos_barras.push(codigo);
+                Ok(())
+            }
+            None => Err("Producto no encontrado".to_string()),
+        }
+    }
+
+    pub fn buscar_por_codigo(&self, codigo: &str) -> Option<String> {
+        let codigo = normalizar_codigo(codigo);
+        self.productos
+            .values()
+            .find(|p| p.codigos_barras.contains(&codigo))
+            .map(|p| p.nombre.clone())
+    }
+
+    // Asigna un EAN-13 interno con el prefijo de la tienda y el siguiente número libre
+    pub fn generar_codigo_interno(&mut self, nombre: &str) -> Result<String, String> {
+        if !self.productos.contains_key(nombre) {
+            return Err("Producto no encontrado".to_string());
+        }
+        let siguiente = self
+            .productos
+            .values()
+            .flat_map(|p| p.codigos_barras.iter())
+            .filter(|c| c.len() == 13 && c.starts_with(PREFIJO_INTERNO))
+            .filter_map(|c| c[2..12].parse::<u64>().ok())
+            .max()
+            .map_or(1, |n| n + 1);
+        let datos = format!("{}{:010}", PREFIJO_INTERNO, siguiente);
+        let codigo = format!("{}{}", datos, digito_verificador(&datos));
+        self.agregar_codigo_barras(nombre, &codigo)?;
+        Ok(codigo)
+    }
+}
+
+pub fn menu_codigos_barras(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Códigos de Barras");
+        println!("-----------------");
+        println!("1. Agregar Código a un Producto");
+        println!("2. Generar Código Interno");
+        println!("3. Buscar Producto por Código");
+        println!("4. Exportar Imagen del Código");
+        println!("5. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el nombre o SKU del producto:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+                println!("Escanee o ingrese el código de barras:");
+                let codigo = leer_entrada();
+                match gestor_productos.agregar_codigo_barras(&nombre, &codigo) {
+                    Ok(()) => println!("Código agregado exitosamente"),
+                    Err(err) => println!("Error al agregar el código: {}", err),
+                }
+            }
+            "2" => {
+                println!("Ingrese el nombre o SKU del producto:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+                match gestor_productos.generar_codigo_interno(&nombre) {
+                    Ok(codigo) => println!("Código generado: {}", codigo),
+                    Err(err) => println!("Error al generar el código: {}", err),
+                }
+            }
+            "3" => {
+                println!("Escanee o ingrese el código de barras:");
+                let codigo = leer_entrada();
+                match gestor_productos
+                    .buscar_por_codigo(&codigo)
+                    .and_then(|nombre| gestor_productos.buscar_producto(&nombre))
+                {
+                    Some(producto) => producto.mostrar_detalle(),
+                    None => println!("Producto no encontrado"),
+                }
+            }
+            "4" => {
+                println!("Ingrese el nombre, SKU o código de barras del producto:");
+                let nombre = leer_entrada();
+                let producto = gestor_productos
+                    .resolver_producto(&nombre)
+                    .and_then(|nombre| gestor_productos.buscar_producto(&nombre));
+                let Some(producto) = producto else {
+                    println!("Producto no encontrado");
+                    pausar();
+                    continue;
+                };
+                let Some(codigo) = producto.codigos_barras.first() else {
+                    println!("El producto no tiene código de barras, genere uno primero");
+                    pausar();
+                    continue;
+                };
+
+                println!("Seleccione el formato (svg/png):");
+                let resultado = match leer_entrada().to_lowercase().as_str() {
+                    "svg" => {
+                        let ruta = format!("{}.svg", producto.sku);
+                        generar_svg(codigo)
+                            .and_then(|svg| {
+                                std::fs::write(&ruta, svg).map_err(|err| err.to_string())
+                            })
+                            .map(|()| ruta)
+                    }
+                    "png" => {
+                        let ruta = format!("{}.png", producto.sku);
+                        guardar_png(codigo, &ruta).map(|()| ruta)
+                    }
+                    _ => Err("Formato no válido".to_string()),
+                };
+                match resultado {
+                    Ok(ruta) => println!("Imagen guardada en {}", ruta),
+                    Err(err) => println!("Error al exportar la imagen: {}", err),
+                }
+            }
+            "5" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texto_modulos(codigo: &str) -> String {
+        modulos_codigo(codigo)
+            .unwrap()
+            .into_iter()
+            .map(|barra| if barra { '1' } else { '0' })
+            .collect()
+    }
+
+    // Convierte anchos de barras y espacios alternados en módulos
+    fn anchos_a_modulos(anchos: &str) -> String {
+        anchos
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .enumerate()
+            .map(|(i, ancho)| {
+                let modulo = if i % 2 == 0 { "1" } else { "0" };
+                modulo.repeat(ancho as usize)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn digito_verificador_conocido() {
+        assert_eq!(digito_verificador("400638133393"), 1);
+        assert_eq!(digito_verificador("590123412345"), 7);
+        assert_eq!(digito_verificador("750103131130"), 9);
+        assert_eq!(digito_verificador("03600029145"), 2);
+    }
+
+    #[test]
+    fn validar_tipos_de_codigo() {
+        assert_eq!(validar_codigo("4006381333931"), Ok(TipoCodigo::Ean13));
+        assert_eq!(validar_codigo("036000291452"), Ok(TipoCodigo::UpcA));
+        assert_eq!(validar_codigo("ABC-123"), Ok(TipoCodigo::Code128));
+        assert!(validar_codigo("4006381333932").is_err());
+        assert!(validar_codigo("").is_err());
+    }
+
+    #[test]
+    fn patron_ean13() {
+        assert_eq!(
+            texto_modulos("4006381333931"),
+            "10100011010100111010111101111010001001011001101010100001010000101000010111010010000101100110101"
+        );
+    }
+
+    #[test]
+    fn patron_ean13_con_primer_digito_siete() {
+        assert_eq!(
+            texto_modulos("7501031311309"),
+            "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101"
+        );
+    }
+
+    #[test]
+    fn patron_upca_igual_a_su_ean13() {
+        let esperado = "10100011010111101010111100011010001101000110101010110110011101001100110101110010011101101100101";
+        assert_eq!(texto_modulos("036000291452"), esperado);
+        assert_eq!(texto_modulos("0036000291452"), esperado);
+    }
+
+    #[test]
+    fn patron_code128() {
+        // Inicio B, A, B, verificador (104 + 33 + 34 * 2) % 103 = 102 y fin
+        assert_eq!(
+            texto_modulos("AB"),
+            anchos_a_modulos("211214 111323 131123 411131 2331112")
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::GestorProductos;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+// Registro de una compra hecha desde el menú Comprar Producto
+#[derive(Serialize, Deserialize)]
+pub struct Compra {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub proveedor: String,
+    pub producto: String,
+    pub cantidad: f64,
+    pub costo_unitario: f64,
+    pub usuario: String,
+}
+
+// Implementación de métodos de compras para GestorProductos
+impl GestorProductos {
+    // Si no se indica costo se mantiene el costo actual del producto
+    pub fn registrar_compra(
+        &mut self,
+        nombre: &str,
+        cantidad: f64,
+        costo_unitario: Option<f64>,
+        proveedor: &str,
+        usuario: &str,
+    ) -> Result<u32, String> {
+        if cantidad <= 0.0 {
+            return Err("Cantidad no válida".to_string());
+        }
+        match costo_unitario {
+            Some(costo) if costo < 0.0 => return Err("Costo no válido".to_string()),
+            Some(costo) => self.comprar_producto_con_costo(nombre, cantidad, costo)?,
+            None => self.comprar_producto(nombre, cantidad)?,
+        }
+
+        let id = self.compras.len() as u32 + 1;
+        let costo_unitario =
+            costo_unitario.unwrap_or_else(|| self.productos.get(nombre).map_or(0.0, |p| p.costo));
+        self.compras.push(Compra {
+            id,
+            fecha: Local::now(),
+            proveedor: proveedor.to_string(),
+            producto: nombre.to_string(),
+            cantidad,
+            costo_unitario,
+            usuario: usuario.to_string(),
+        });
+        Ok(id)
+    }
+
+    pub fn buscar_compra(&self, id: u32) -> Option<&Compra> {
+        self.compras.iter().find(|c| c.id == id)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LineaOrdenCompra {
+    pub producto: String,
+    pub cantidad: f64,
+    pub costo_unitario: f64,
+}
+
+// Pedido a un proveedor, al recibirlo se registra una compra por cada línea
+#[derive(Serialize, Deserialize)]
+pub struct OrdenCompra {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub proveedor: String,
+    pub lineas: Vec<LineaOrdenCompra>,
+    pub usuario: String,
+    pub recibida: Option<DateTime<Local>>,
+}
+
+impl OrdenCompra {
+    pub fn total(&self) -> f64 {
+        self.lineas
+            .iter()
+            .map(|l| l.cantidad * l.costo_unitario)
+            .sum()
+    }
+
+    pub fn mostrar(&self) {
+        println!(
+            "Orden de compra #{} - {} - {} - {}",
+            self.id,
+            self.fecha.format("%Y-%m-%d"),
+            self.proveedor,
+            match self.recibida {
+                Some(fecha) => format!("recibida el {}", fecha.format("%Y-%m-%d")),
+                None => "pendiente".to_string(),
+            }
+        );
+        for linea in &self.lineas {
+            println!(
+                "  {} x {} @ ${:.2}",
+                linea.cantidad, linea.producto, linea.costo_unitario
+            );
+        }
+        println!(
+            "  Total: ${:.2} - Creada por {}",
+            self.total(),
+            self.usuario
+        );
+    }
+}
+
+// Implementación de métodos de órdenes de compra para GestorProductos
+impl GestorProductos {
+    pub fn crear_orden_compra(
+        &mut self,
+        proveedor: &str,
+        lineas: Vec<LineaOrdenCompra>,
+        usuario: &str,
+    ) -> Result<u32, String> {
+        if lineas.is_empty() {
+            return Err("La orden no tiene productos".to_string());
+        }
+        for linea in &lineas {
+            match self.productos.get(&linea.producto) {
+                None => return Err(format!("Producto no encontrado: {}", linea.producto)),
+                Some(producto) if !producto.componentes.is_empty() => {
+                    return Err(format!(
+                        "{} es un kit, pida sus componentes",
+                        linea.producto
+                    ))
+                }
+                Some(_) if linea.cantidad <= 0.0 || linea.costo_unitario < 0.0 => {
+                    return Err(format!(
+                        "Cantidad o costo no válidos para {}",
+                        linea.producto
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        let id = self.ordenes_compra.len() as u32 + 1;
+        self.ordenes_compra.push(OrdenCompra {
+            id,
+            fecha: Local::now(),
+            proveedor: proveedor.to_string(),
+            lineas,
+            usuario: usuario.to_string(),
+            recibida: None,
+        });
+        Ok(id)
+    }
+
+    // Cantidad pedida a proveedores que todavía no se ha recibido
+    pub fn cantidad_en_pedido(&self, producto: &str) -> f64 {
+        self.ordenes_compra
+            .iter()
+            .filter(|o| o.recibida.is_none())
+            .flat_map(|o| o.lineas.iter())
+            .filter(|l| l.producto == producto)
+            .map(|l| l.cantidad)
+            .sum()
+    }
+
+    // Registra una compra por línea y devuelve sus números
+    pub fn recibir_orden_compra(&mut self, id: u32, usuario: &str) -> Result<Vec<u32>, String> {
+        let orden = self
+            .ordenes_compra
+            .iter()
+            .find(|o| o.id == id)
+            .ok_or_else(|| "Orden de compra no encontrada".to_string())?;
+        if orden.recibida.is_some() {
+            return Err("La orden ya fue recibida".to_string());
+        }
+        let proveedor = orden.proveedor.clone();
+        let lineas: Vec<(String, f64, f64)> = orden
+            .lineas
+            .iter()
+            .map(|l| (l.producto.clone(), l.cantidad, l.costo_unitario))
+            .collect();
+
+        // Se valida toda la orden antes de registrar, así una línea inválida no deja la orden a medias
+        for (producto, cantidad, costo_unitario) in &lineas {
+            match self.productos.get(producto) {
+                None => return Err(format!("Producto no encontrado: {}", producto)),
+                Some(p) if !p.componentes.is_empty() => {
+                    return Err(format!("{} es un kit, compre sus componentes", producto))
+                }
+                Some(_) if *cantidad <= 0.0 => {
+                    return Err(format!("Cantidad no válida para {}", producto))
+                }
+                Some(_) if *costo_unitario < 0.0 => {
+                    return Err(format!("Costo no válido para {}", producto))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut compras = Vec::new();
+        for (producto, cantidad, costo_unitario) in lineas {
+            compras.push(self.registrar_compra(
+                &producto,
+                cantidad,
+                Some(costo_unitario),
+                &proveedor,
+                usuario,
+            )?);
+        }
+        if let Some(orden) = self.ordenes_compra.iter_mut().find(|o| o.id == id) {
+            orden.recibida = Some(Local::now());
+        }
+        Ok(compras)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::reportes_pdf::cargar_logo;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use serde::{Deserialize, Serialize};
+
+// Parámetros generales de la tienda
+#[derive(Serialize, Deserialize)]
+pub struct Configuracion {
+    // Los precios incluyen impuesto, la tasa se usa para desglosarlo
+    pub tasa_impuesto: f64,
+    pub dias_devolucion: i64,
+    // Porcentaje máximo de descuento manual por rol
+    pub descuento_maximo_usuario: f64,
+    pub descuento_maximo_administrador: f64,
+    // Lista de precios para ventas sin cliente o clientes sin lista asignada
+    pub lista_precios_defecto: Option<String>,
+    // Encabezado de los reportes en PDF, el logo es la ruta de una imagen PNG
+    pub nombre_empresa: String,
+    pub logo: Option<String>,
+}
+
+impl Configuracion {
+    pub fn new() -> Configuracion {
+        Configuracion {
+            tasa_impuesto: 0.12,
+            dias_devolucion: 30,
+            descuento_maximo_usuario: 10.0,
+            descuento_maximo_administrador: 100.0,
+            lista_precios_defecto: None,
+            nombre_empresa: "Chepe te Vende".to_string(),
+            logo: None,
+        }
+    }
+
+    // Parte de un monto con impuesto incluido que corresponde al impuesto
+    pub fn impuesto_incluido(&self, monto: f64) -> f64 {
+        monto - monto / (1.0 + self.tasa_impuesto)
+    }
+}
+
+pub fn menu_configuracion(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        let configuracion = &gestor_productos.configuracion;
+        println!("Configuración");
+        println!("-------------");
+        println!(
+            "1. Tasa de Impuesto (actual: {}%)",
+            configuracion.tasa_impuesto * 100.0
+        );
+        println!(
+            "2. Plazo de Devolución (actual: {} días)",
+            configuracion.dias_devolucion
+        );
+        println!(
+            "3. Descuento Máximo de Usuarios (actual: {}%)",
+            configuracion.descuento_maximo_usuario
+        );
+        println!(
+            "4. Descuento Máximo de Administradores (actual: {}%)",
+            configuracion.descuento_maximo_administrador
+        );
+        println!(
+            "5. Nombre de la Empresa (actual: {})",
+            configuracion.nombre_empresa
+        );
+        println!(
+            "6. Logo para Reportes PDF (actual: {})",
+            configuracion.logo.as_deref().unwrap_or("ninguno")
+        );
+        println!("7. Volver al Menú Principal");
+
+        let opcion = leer_entrada();
+        match opcion.as_str() {
+            "1" => {
+                println!("Ingrese la tasa de impuesto en porcentaje (por ejemplo 12):");
+                match leer_entrada().parse::<f64>() {
+                    Ok(tasa) if (0.0..100.0).contains(&tasa) => {
+                        gestor_productos.configuracion.tasa_impuesto = tasa / 100.0;
+                        println!("Tasa de impuesto actualizada");
+                    }
+                    _ => println!("Tasa no válida"),
+                }
+            }
+            "2" => {
+                println!("Ingrese el número de días para aceptar devoluciones:");
+                match leer_entrada().parse::<i64>() {
+                    Ok(dias) if dias >= 0 => {
+                        gestor_productos.configuracion.dias_devolucion = dias;
+                        println!("Plazo de devolución actualizado");
+                    }
+                    _ => println!("Número de días no válido"),
+                }
+            }
+            "3" | "4" => {
+                println!("Ingrese el porcentaje máximo de descuento manual:");
+                match leer_entrada().parse::<f64>() {
+                    Ok(limite) if (0.0..=100.0).contains(&limite) => {
+                        if opcion == "3" {
+                            gestor_productos.configuracion.descuento_maximo_usuario = limite;
+                        } else {
+                            gestor_productos
+                                .configuracion
+                                .descuento_maximo_administrador = limite;
+                        }
+                        println!("Límite de descuento actualizado");
+                    }
+                    _ => println!("Porcentaje no válido"),
+                }
+            }
+            "5" => {
+                println!("Ingrese el nombre de la empresa:");
+                let nombre = leer_entrada();
+                if nombre.is_empty() {
+                    println!("El nombre no puede estar vacío");
+                } else {
+                    gestor_productos.configuracion.nombre_empresa = nombre;
+                    println!("Nombre de la empresa actualizado");
+                }
+            }
+            "6" => {
+                println!("Ingrese la ruta del logo en PNG (deje vacío para no usar logo):");
+                let ruta = leer_entrada();
+                if ruta.is_empty() {
+                    gestor_productos.configuracion.logo = None;
+                    println!("Logo eliminado");
+                } else {
+                    match cargar_logo(&ruta) {
+                        Ok(_) => {
+                            gestor_productos.configuracion.logo = Some(ruta);
+                            println!("Logo actualizado");
+                        }
+                        Err(err) => println!("No se pudo leer el logo: {}", err),
+                    }
+                }
+            }
+            "7" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,273 @@
+use crate::exportacion::Tabla;
+use crate::importacion::leer_archivo;
+use crate::movimientos::TipoMovimiento;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Producto contado con la existencia que tenía el sistema al aplicar el conteo
+#[derive(Serialize, Deserialize)]
+pub struct LineaConteo {
+    pub producto: String,
+    pub sistema: f64,
+    pub contado: f64,
+}
+
+impl LineaConteo {
+    pub fn diferencia(&self) -> f64 {
+        self.contado - self.sistema
+    }
+}
+
+// Cantidades leídas de una hoja de conteo y errores con su número de fila
+pub struct LecturaConteo {
+    pub contados: Vec<(String, f64)>,
+    pub errores: Vec<(usize, String)>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConteoInventario {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub usuario: String,
+    pub lineas: Vec<LineaConteo>,
+}
+
+impl ConteoInventario {
+    pub fn mostrar(&self) {
+        println!(
+            "Conteo #{} - {} - {} productos - registrado por {}",
+            self.id,
+            self.fecha.format("%Y-%m-%d %H:%M"),
+            self.lineas.len(),
+            self.usuario
+        );
+        for linea in self.lineas.iter().filter(|l| l.diferencia() != 0.0) {
+            println!(
+                "  {} - sistema: {} - contado: {} - diferencia: {:+}",
+                linea.producto,
+                linea.sistema,
+                linea.contado,
+                linea.diferencia()
+            );
+        }
+    }
+}
+
+// Implementación de métodos de conteo de inventario para GestorProductos
+impl GestorProductos {
+    // Hoja para llenar a mano; la columna contado se deja vacía
+    pub fn hoja_conteo(&self) -> Tabla {
+        let mut productos: Vec<_> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty())
+            .collect();
+        productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        Tabla {
+            columnas: vec!["nombre", "sku", "categoria", "sistema", "contado"],
+            filas: productos
+                .into_iter()
+                .map(|p| {
+                    vec![
+                        json!(p.nombre),
+                        json!(p.sku),
+                        p.categoria
+                            .map_or(Value::Null, |c| json!(self.ruta_categoria(c))),
+                        json!(p.cantidad_disponible),
+                        Value::Null,
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    // Devuelve las cantidades contadas y los errores por fila; las filas sin contar se ignoran
+    pub fn leer_hoja_conteo(
+        &self,
+        encabezados: &[String],
+        filas: &[Vec<String>],
+    ) -> Result<LecturaConteo, String> {
+        let columna = |nombre: &str| {
+            encabezados
+                .iter()
+                .position(|e| e.trim().eq_ignore_ascii_case(nombre))
+        };
+        let contado = columna("contado").ok_or("La hoja no tiene la columna contado")?;
+        let identificadores: Vec<usize> = [columna("nombre"), columna("sku")]
+            .into_iter()
+            .flatten()
+            .collect();
+        if identificadores.is_empty() {
+            return Err("La hoja no tiene la columna nombre ni sku".to_string());
+        }
+
+        let mut contados: Vec<(String, f64)> = Vec::new();
+        let mut errores = Vec::new();
+        for (i, fila) in filas.iter().enumerate() {
+            let numero_fila = i + 2;
+            let valor = fila.get(contado).map_or("", |v| v.trim());
+            if valor.is_empty() {
+                continue;
+            }
+            let producto = identificadores
+                .iter()
+                .filter_map(|&c| fila.get(c))
+                .find_map(|entrada| self.resolver_producto(entrada.trim()));
+            let Some(producto) = producto else {
+                errores.push((numero_fila, "Producto no encontrado".to_string()));
+                continue;
+            };
+            match valor.replace(',', ".").parse::<f64>() {
+                Ok(cantidad) if cantidad >= 0.0 => {
+                    if contados.iter().any(|(p, _)| *p == producto) {
+                        errores.push((numero_fila, format!("{} está repetido", producto)));
+                    } else {
+                        contados.push((producto, cantidad));
+                    }
+                }
+                _ => errores.push((numero_fila, format!("Cantidad no válida: {}", valor))),
+            }
+        }
+        Ok(LecturaConteo { contados, errores })
+    }
+
+    // Fija la existencia de cada producto contado y registra la diferencia como movimiento
+    pub fn registrar_conteo(
+        &mut self,
+        contados: &[(String, f64)],
+        usuario: &str,
+    ) -> Result<u32, String> {
+        if contados.is_empty() {
+            return Err("No hay productos contados".to_string());
+        }
+        for (nombre, cantidad) in contados {
+            match self.productos.get(nombre) {
+                None => return Err(format!("Producto no encontrado: {}", nombre)),
+                Some(p) if !p.componentes.is_empty() => {
+                    return Err(format!("{} es un kit y no se cuenta", nombre))
+                }
+                Some(_) if *cantidad < 0.0 => {
+                    return Err(format!("Cantidad no válida para {}", nombre))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut lineas = Vec::new();
+        for (nombre, cantidad) in contados {
+            let Some(producto) = self.productos.get_mut(nombre) else {
+                continue;
+            };
+            let sistema = producto.cantidad_disponible;
+            let costo = producto.costo;
+            producto.cantidad_disponible = *cantidad;
+            self.registrar_movimiento(nombre, TipoMovimiento::Conteo, cantidad - sistema, costo);
+            lineas.push(LineaConteo {
+                producto: nombre.clone(),
+                sistema,
+                contado: *cantidad,
+            });
+        }
+        let id = self.conteos.len() as u32 + 1;
+        self.conteos.push(ConteoInventario {
+            id,
+            fecha: Local::now(),
+            usuario: usuario.to_string(),
+            lineas,
+        });
+        Ok(id)
+    }
+}
+
+pub fn menu_conteos(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Conteo de Inventario");
+        println!("--------------------");
+        println!("1. Exportar Hoja de Conteo (XLSX)");
+        println!("2. Importar Hoja de Conteo (XLSX o CSV)");
+        println!("3. Ver Conteos Registrados");
+        println!("4. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el archivo de destino (.xlsx):");
+                let archivo = leer_entrada();
+                if archivo.is_empty() {
+                    println!("No se exportó nada");
+                } else {
+                    match gestor_productos
+                        .hoja_conteo()
+                        .guardar_xlsx(&archivo, "Conteo")
+                    {
+                        Ok(()) => println!("Hoja de conteo guardada en {}", archivo),
+                        Err(err) => println!("Error al guardar la hoja: {}", err),
+                    }
+                }
+            }
+            "2" => {
+                println!("Ingrese la ruta de la hoja de conteo:");
+                let ruta = leer_entrada();
+                let lectura = leer_archivo(&ruta).and_then(|(encabezados, filas)| {
+                    gestor_productos.leer_hoja_conteo(&encabezados, &filas)
+                });
+                let LecturaConteo { contados, errores } = match lectura {
+                    Ok(datos) => datos,
+                    Err(err) => {
+                        println!("Error al leer la hoja: {}", err);
+                        pausar();
+                        continue;
+                    }
+                };
+                for (fila, error) in &errores {
+                    println!("Fila {}: {}", fila, error);
+                }
+                for (nombre, cantidad) in &contados {
+                    let sistema = gestor_productos
+                        .buscar_producto(nombre)
+                        .map_or(0.0, |p| p.cantidad_disponible);
+                    if *cantidad != sistema {
+                        println!(
+                            "{} - sistema: {} - contado: {} - diferencia: {:+}",
+                            nombre,
+                            sistema,
+                            cantidad,
+                            cantidad - sistema
+                        );
+                    }
+                }
+                println!(
+                    "{} productos contados, {} filas con errores",
+                    contados.len(),
+                    errores.len()
+                );
+                println!("¿Aplicar el conteo? (s/n):");
+                if leer_entrada().to_lowercase() == "s" {
+                    match gestor_productos.registrar_conteo(&contados, usuario_actual) {
+                        Ok(id) => println!("Conteo #{} registrado", id),
+                        Err(err) => println!("Error: {}", err),
+                    }
+                } else {
+                    println!("No se aplicó el conteo");
+                }
+            }
+            "3" => {
+                if gestor_productos.conteos.is_empty() {
+                    println!("No hay conteos registrados");
+                }
+                for conteo in &gestor_productos.conteos {
+                    conteo.mostrar();
+                }
+            }
+            "4" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,121 @@
+use crate::pagos::TOLERANCIA;
+use crate::ventas::LineaVenta;
+use crate::{leer_entrada, GestorProductos};
+use serde::{Deserialize, Serialize};
+
+// Descuento manual sobre una línea o sobre toda la venta
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Descuento {
+    Porcentaje(f64),
+    Monto(f64),
+}
+
+impl Descuento {
+    // Acepta "10%" como porcentaje y "5" como monto fijo
+    pub fn interpretar(texto: &str) -> Option<Descuento> {
+        let descuento = match texto.trim().strip_suffix('%') {
+            Some(porcentaje) => Descuento::Porcentaje(porcentaje.trim().parse().ok()?),
+            None => Descuento::Monto(texto.trim().parse().ok()?),
+        };
+        match descuento {
+            Descuento::Porcentaje(p) if (0.0..=100.0).contains(&p) => Some(descuento),
+            Descuento::Monto(m) if m >= 0.0 => Some(descuento),
+            _ => None,
+        }
+    }
+
+    // Monto a descontar de la base, nunca mayor que la base
+    pub fn monto(&self, base: f64) -> f64 {
+        let monto = match self {
+            Descuento::Porcentaje(porcentaje) => base * porcentaje / 100.0,
+            Descuento::Monto(monto) => *monto,
+        };
+        monto.clamp(0.0, base.max(0.0))
+    }
+
+    pub fn descripcion(&self) -> String {
+        match self {
+            Descuento::Porcentaje(porcentaje) => format!("{}%", porcentaje),
+            Descuento::Monto(monto) => format!("${:.2}", monto),
+        }
+    }
+}
+
+// Implementación de métodos de descuentos para GestorProductos
+impl GestorProductos {
+    // Porcentaje máximo de descuento manual que puede dar el usuario según su rol
+    pub fn limite_descuento(&self, es_administrador: bool) -> f64 {
+        if es_administrador {
+            self.configuracion.descuento_maximo_administrador
+        } else {
+            self.configuracion.descuento_maximo_usuario
+        }
+    }
+
+    pub fn validar_descuento(
+        &self,
+        base: f64,
+        descuento: &Descuento,
+        es_administrador: bool,
+    ) -> Result<(), String> {
+        if base <= 0.0 {
+            return Ok(());
+        }
+        let limite = self.limite_descuento(es_administrador);
+        let porcentaje = descuento.monto(base) / base * 100.0;
+        if porcentaje > limite + TOLERANCIA {
+            return Err(format!(
+                "El descuento equivale a {:.2}% y su límite es {}%",
+                porcentaje, limite
+            ));
+        }
+        Ok(())
+    }
+
+    // Revisa los descuentos de cada línea y el de la venta con los montos actuales, porque
+    // un descuento fijo puede superar el límite si después bajan las cantidades o los precios
+    pub fn validar_descuentos_venta(
+        &self,
+        lineas: &[LineaVenta],
+        descuento: Option<Descuento>,
+        es_administrador: bool,
+    ) -> Result<(), String> {
+        for linea in lineas {
+            if let Some(descuento) = &linea.descuento {
+                let base = linea.bruto() - linea.descuento_promocion();
+                self.validar_descuento(base, descuento, es_administrador)
+                    .map_err(|err| format!("{}: {}", linea.producto, err))?;
+            }
+        }
+        if let Some(descuento) = &descuento {
+            let suma = lineas.iter().map(LineaVenta::subtotal).sum();
+            self.validar_descuento(suma, descuento, es_administrador)
+                .map_err(|err| format!("Descuento de la venta: {}", err))?;
+        }
+        Ok(())
+    }
+}
+
+// Pide un descuento y lo valida contra el límite del rol, None si se deja vacío o no es válido
+pub fn pedir_descuento(
+    gestor_productos: &GestorProductos,
+    base: f64,
+    es_administrador: bool,
+) -> Option<Descuento> {
+    println!("Ingrese el descuento (10% o monto fijo, deje vacío para ninguno):");
+    let entrada = leer_entrada();
+    if entrada.is_empty() {
+        return None;
+    }
+    let Some(descuento) = Descuento::interpretar(&entrada) else {
+        println!("Descuento no válido");
+        return None;
+    };
+    match gestor_productos.validar_descuento(base, &descuento, es_administrador) {
+        Ok(()) => Some(descuento),
+        Err(err) => {
+            println!("{}", err);
+            None
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use crate::movimientos::TipoMovimiento;
+use crate::pagos::MetodoPago;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct LineaDevolucion {
+    pub producto: String,
+    pub cantidad: f64,
+    pub precio_unitario: f64,
+    // true si la mercadería vuelve al inventario, false si se da de baja
+    pub reingresar: bool,
+}
+
+// Devolución de un cliente que hace referencia a la venta original
+#[derive(Serialize, Deserialize)]
+pub struct Devolucion {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub venta: u32,
+    pub usuario: String,
+    pub lineas: Vec<LineaDevolucion>,
+    pub reembolso: f64,
+    pub impuesto: f64,
+    pub metodo: MetodoPago,
+    pub sesion: Option<u32>,
+    pub motivo: String,
+}
+
+// Implementación de métodos de devoluciones para GestorProductos
+impl GestorProductos {
+    pub fn cantidad_devuelta(&self, venta: u32, producto: &str) -> f64 {
+        self.devoluciones
+            .iter()
+            .filter(|d| d.venta == venta)
+            .flat_map(|d| d.lineas.iter())
+            .filter(|l| l.producto == producto)
+            .map(|l| l.cantidad)
+            .sum()
+    }
+
+    // Regresa unidades al inventario sin pasar por comprar_producto para no alterar el costo
+    fn reingresar_producto(&mut self, nombre: &str, cantidad: f64) {
+        let componentes = match self.productos.get(nombre) {
+            Some(producto) => producto.componentes.clone(),
+            None => return,
+        };
+        let entradas = if componentes.is_empty() {
+            vec![(nombre.to_string(), cantidad)]
+        } else {
+            componentes
+                .into_iter()
+                .map(|(componente, por_kit)| (componente, por_kit * cantidad))
+                .collect()
+        };
+        for (producto, cantidad) in entradas {
+            if let Some(inventario) = self.productos.get_mut(&producto) {
+                inventario.cantidad_disponible += cantidad;
+                let costo = inventario.costo;
+                self.registrar_movimiento(&producto, TipoMovimiento::Devolucion, cantidad, costo);
+            }
+        }
+    }
+
+    // Cada línea es (producto, cantidad, reingresar al inventario)
+    pub fn registrar_devolucion(
+        &mut self,
+        venta_id: u32,
+        lineas: Vec<(String, f64, bool)>,
+        metodo: MetodoPago,
+        motivo: &str,
+        usuario: &str,
+    ) -> Result<u32, String> {
+        let venta = self
+            .buscar_venta(venta_id)
+            .ok_or_else(|| "Venta no encontrada".to_string())?;
+        let dias = (Local::now() - venta.fecha).num_days();
+        if dias > self.configuracion.dias_devolucion {
+            return Err(format!(
+                "La venta tiene {} días y el plazo de devolución es de {} días",
+                dias, self.configuracion.dias_devolucion
+            ));
+        }
+        if lineas.is_empty() {
+            return Err("La devolución no tiene productos".to_string());
+        }
+
+        // Se reembolsa lo cobrado, con los descuentos de línea y de la venta prorrateados
+        let suma: f64 = venta.lineas.iter().map(|l| l.subtotal()).sum();
+        let proporcion = if suma > 0.0 { venta.total / suma } else { 0.0 };
+
+        let mut lineas_devolucion: Vec<LineaDevolucion> = Vec::new();
+        for (producto, cantidad, reingresar) in lineas {
+            let vendidas: Vec<_> = venta
+                .lineas
+                .iter()
+                .filter(|l| l.producto == producto)
+                .collect();
+            let Some(linea_venta) = vendidas.first() else {
+                return Err(format!("{} no forma parte de la venta", producto));
+            };
+            let vendido: f64 = vendidas.iter().map(|l| l.cantidad).sum();
+            let pendiente: f64 = lineas_devolucion
+                .iter()
+                .filter(|l| l.producto == producto)
+                .map(|l| l.cantidad)
+                .sum();
+            let disponible = vendido - self.cantidad_devuelta(venta_id, &producto) - pendiente;
+            if cantidad <= 0.0 || cantidad > disponible {
+                return Err(format!(
+                    "Cantidad no válida para {}, se pueden devolver {}",
+                    producto, disponible
+                ));
+            }
+            lineas_devolucion.push(LineaDevolucion {
+                precio_unitario: linea_venta.subtotal() / linea_venta.cantidad * proporcion,
+                producto,
+                cantidad,
+                reingresar,
+            });
+        }
+
+        for linea in &lineas_devolucion {
+            if linea.reingresar {
+                self.reingresar_producto(&linea.producto, linea.cantidad);
+            }
+            if let Some(producto) = self.productos.get_mut(&linea.producto) {
+                producto.vendido -= linea.cantidad;
+            }
+        }
+
+        let reembolso: f64 = lineas_devolucion
+            .iter()
+            .map(|l| l.cantidad * l.precio_unitario)
+            .sum();
+        let id = self.devoluciones.len() as u32 + 1;
+        self.devoluciones.push(Devolucion {
+            id,
+            fecha: Local::now(),
+            venta: venta_id,
+            usuario: usuario.to_string(),
+            lineas: lineas_devolucion,
+            reembolso,
+            impuesto: self.configuracion.impuesto_incluido(reembolso),
+            metodo,
+            sesion: self.sesion_abierta(usuario),
+            motivo: motivo.to_string(),
+        });
+        Ok(id)
+    }
+}
+
+impl Devolucion {
+    pub fn mostrar(&self) {
+        println!(
+            "Devolución #{} - {} - Venta #{}",
+            self.id,
+            self.fecha.format("%Y-%m-%d %H:%M"),
+            self.venta
+        );
+        println!("Atendido por: {}", self.usuario);
+        for linea in &self.lineas {
+            println!(
+                "{} x {} @ ${:.2} - {}",
+                linea.cantidad,
+                linea.producto,
+                linea.precio_unitario,
+                if linea.reingresar {
+                    "reingresado"
+                } else {
+                    "dado de baja"
+                }
+            );
+        }
+        println!("Motivo: {}", self.motivo);
+        println!(
+            "Reembolso: ${:.2} (impuesto incluido: ${:.2})",
+            self.reembolso, self.impuesto
+        );
+        println!("Método de reembolso: {}", self.metodo.nombre());
+    }
+}
+
+pub fn menu_devoluciones(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Devoluciones de Clientes");
+        println!("------------------------");
+        println!("1. Registrar Devolución");
+        println!("2. Ver Devoluciones");
+        println!("3. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el número de la venta original:");
+                let Some(venta) = leer_entrada()
+                    .trim_start_matches('#')
+                    .parse::<u32>()
+                    .ok()
+                    .and_then(|id| gestor_productos.buscar_venta(id))
+                else {
+                    println!("Venta no encontrada");
+                    pausar();
+                    continue;
+                };
+                venta.mostrar_ticket();
+                let venta_id = venta.id;
+                let productos: Vec<String> =
+                    venta.lineas.iter().map(|l| l.producto.clone()).collect();
+
+                let mut lineas = Vec::new();
+                for producto in productos {
+                    let devuelto = gestor_productos.cantidad_devuelta(venta_id, &producto);
+                    println!(
+                        "Cantidad a devolver de {} (ya devuelto: {}, deje vacío para 0):",
+                        producto, devuelto
+                    );
+                    let cantidad = match leer_entrada().parse::<f64>() {
+                        Ok(cantidad) if cantidad > 0.0 => cantidad,
+                        _ => continue,
+                    };
+                    println!("¿La mercadería vuelve al inventario? (s/n, n la da de baja):");
+                    let reingresar = leer_entrada().to_lowercase() == "s";
+                    lineas.push((producto, cantidad, reingresar));
+                }
+
+                println!("Ingrese el motivo de la devolución:");
+                let motivo = leer_entrada();
+
+                println!("Seleccione el método de reembolso:");
+                for (i, metodo) in MetodoPago::TODOS.iter().enumerate() {
+                    println!("{}. {}", i + 1, metodo.nombre());
+                }
+                let metodo = match leer_entrada().parse::<usize>() {
+                    Ok(opcion) if (1..=MetodoPago::TODOS.len()).contains(&opcion) => {
+                        MetodoPago::TODOS[opcion - 1]
+                    }
+                    _ => {
+                        println!("Método no válido");
+                        pausar();
+                        continue;
+                    }
+                };
+
+                match gestor_productos.registrar_devolucion(
+                    venta_id,
+                    lineas,
+                    metodo,
+                    &motivo,
+                    usuario_actual,
+                ) {
+                    Ok(id) => {
+                        println!();
+                        if let Some(devolucion) =
+                            gestor_productos.devoluciones.iter().find(|d| d.id == id)
+                        {
+                            devolucion.mostrar();
+                        }
+                    }
+                    Err(err) => println!("Error al registrar la devolución: {}", err),
+                }
+            }
+            "2" => {
+                if gestor_productos.devoluciones.is_empty() {
+                    println!("No hay devoluciones registradas");
+                }
+                for devolucion in &gestor_productos.devoluciones {
+                    devolucion.mostrar();
+                    println!();
+                }
+            }
+            "3" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,277 @@
+use crate::movimientos::TipoMovimiento;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub enum EstadoCredito {
+    Pendiente,
+    Acreditada {
+        numero: String,
+        fecha: DateTime<Local>,
+        monto: f64,
+    },
+}
+
+// Mercadería devuelta al proveedor, queda pendiente hasta recibir su nota de crédito
+#[derive(Serialize, Deserialize)]
+pub struct DevolucionProveedor {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub compra: u32,
+    pub proveedor: String,
+    pub producto: String,
+    pub cantidad: f64,
+    pub costo_unitario: f64,
+    pub motivo: String,
+    pub usuario: String,
+    pub estado: EstadoCredito,
+}
+
+impl DevolucionProveedor {
+    pub fn credito_esperado(&self) -> f64 {
+        self.cantidad * self.costo_unitario
+    }
+
+    pub fn mostrar(&self) {
+        println!(
+            "#{} - {} - Compra #{} - {} - Registrada por {}",
+            self.id,
+            self.fecha.format("%Y-%m-%d"),
+            self.compra,
+            self.proveedor,
+            self.usuario
+        );
+        println!(
+            "  {} x {} @ ${:.2} - Motivo: {}",
+            self.cantidad, self.producto, self.costo_unitario, self.motivo
+        );
+        match &self.estado {
+            EstadoCredito::Pendiente => println!(
+                "  Nota de crédito pendiente: ${:.2}",
+                self.credito_esperado()
+            ),
+            EstadoCredito::Acreditada {
+                numero,
+                fecha,
+                monto,
+            } => println!(
+                "  Acreditada con nota {} el {} por ${:.2}",
+                numero,
+                fecha.format("%Y-%m-%d"),
+                monto
+            ),
+        }
+    }
+}
+
+// Implementación de métodos de devoluciones a proveedor para GestorProductos
+impl GestorProductos {
+    pub fn cantidad_devuelta_proveedor(&self, compra: u32) -> f64 {
+        self.devoluciones_proveedor
+            .iter()
+            .filter(|d| d.compra == compra)
+            .map(|d| d.cantidad)
+            .sum()
+    }
+
+    pub fn registrar_devolucion_proveedor(
+        &mut self,
+        compra_id: u32,
+        cantidad: f64,
+        motivo: &str,
+        usuario: &str,
+    ) -> Result<u32, String> {
+        let compra = self
+            .buscar_compra(compra_id)
+            .ok_or_else(|| "Compra no encontrada".to_string())?;
+        let pendiente = compra.cantidad - self.cantidad_devuelta_proveedor(compra_id);
+        if cantidad <= 0.0 || cantidad > pendiente {
+            return Err(format!(
+                "Cantidad no válida, se pueden devolver {} de esta compra",
+                pendiente
+            ));
+        }
+        let (producto, proveedor, costo_unitario) = (
+            compra.producto.clone(),
+            compra.proveedor.clone(),
+            compra.costo_unitario,
+        );
+
+        let inventario = self
+            .productos
+            .get_mut(&producto)
+            .ok_or_else(|| "Producto no encontrado".to_string())?;
+        if inventario.cantidad_disponible < cantidad {
+            return Err("Cantidad insuficiente en el inventario".to_string());
+        }
+        let costo_anterior = inventario.costo;
+        // Se retiran las unidades a su costo de compra para que el promedio siga siendo correcto
+        let restante = inventario.cantidad_disponible - cantidad;
+        if restante > 0.0 {
+            let valor =
+                inventario.costo * inventario.cantidad_disponible - costo_unitario * cantidad;
+            inventario.costo = (valor / restante).max(0.0);
+        }
+        inventario.cantidad_disponible = restante;
+        self.registrar_movimiento(
+            &producto,
+            TipoMovimiento::DevolucionProveedor,
+            -cantidad,
+            costo_anterior,
+        );
+
+        let id = self.devoluciones_proveedor.len() as u32 + 1;
+        self.devoluciones_proveedor.push(DevolucionProveedor {
+            id,
+            fecha: Local::now(),
+            compra: compra_id,
+            proveedor,
+            producto,
+            cantidad,
+            costo_unitario,
+            motivo: motivo.to_string(),
+            usuario: usuario.to_string(),
+            estado: EstadoCredito::Pendiente,
+        });
+        Ok(id)
+    }
+
+    pub fn registrar_nota_credito(
+        &mut self,
+        id: u32,
+        numero: &str,
+        monto: f64,
+    ) -> Result<(), String> {
+        let devolucion = self
+            .devoluciones_proveedor
+            .iter_mut()
+            .find(|d| d.id == id)
+            .ok_or_else(|| "Devolución no encontrada".to_string())?;
+        if !matches!(devolucion.estado, EstadoCredito::Pendiente) {
+            return Err("La devolución ya fue acreditada".to_string());
+        }
+        if numero.is_empty() || monto < 0.0 {
+            return Err("Número o monto de la nota de crédito no válidos".to_string());
+        }
+        devolucion.estado = EstadoCredito::Acreditada {
+            numero: numero.to_string(),
+            fecha: Local::now(),
+            monto,
+        };
+        Ok(())
+    }
+}
+
+pub fn menu_devoluciones_proveedor(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Devoluciones a Proveedor");
+        println!("------------------------");
+        println!("1. Ver Compras");
+        println!("2. Registrar Devolución a Proveedor");
+        println!("3. Registrar Nota de Crédito Recibida");
+        println!("4. Ver Devoluciones a Proveedor");
+        println!("5. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                if gestor_productos.compras.is_empty() {
+                    println!("No hay compras registradas");
+                }
+                for compra in &gestor_productos.compras {
+                    println!(
+                        "#{} - {} - {} - {} - {} x {} @ ${:.2} (devuelto: {})",
+                        compra.id,
+                        compra.fecha.format("%Y-%m-%d"),
+                        compra.usuario,
+                        compra.proveedor,
+                        compra.cantidad,
+                        compra.producto,
+                        compra.costo_unitario,
+                        gestor_productos.cantidad_devuelta_proveedor(compra.id)
+                    );
+                }
+            }
+            "2" => {
+                println!("Ingrese el número de la compra original:");
+                let compra = leer_entrada().trim_start_matches('#').parse::<u32>();
+                println!("Ingrese la cantidad a devolver:");
+                let cantidad = leer_entrada().parse::<f64>();
+                println!("Ingrese el motivo de la devolución:");
+                let motivo = leer_entrada();
+
+                let resultado = match (compra, cantidad) {
+                    (Ok(compra), Ok(cantidad)) => gestor_productos.registrar_devolucion_proveedor(
+                        compra,
+                        cantidad,
+                        &motivo,
+                        usuario_actual,
+                    ),
+                    _ => Err("Número de compra o cantidad no válidos".to_string()),
+                };
+                match resultado {
+                    Ok(id) => println!("Devolución a proveedor #{} registrada", id),
+                    Err(err) => println!("Error al registrar la devolución: {}", err),
+                }
+            }
+            "3" => {
+                println!("Ingrese el número de la devolución a proveedor:");
+                let id = leer_entrada().trim_start_matches('#').parse::<u32>();
+                println!("Ingrese el número de la nota de crédito:");
+                let numero = leer_entrada();
+                let esperado = id
+                    .as_ref()
+                    .ok()
+                    .and_then(|id| {
+                        gestor_productos
+                            .devoluciones_proveedor
+                            .iter()
+                            .find(|d| d.id == *id)
+                    })
+                    .map_or(0.0, |d| d.credito_esperado());
+                println!(
+                    "Ingrese el monto acreditado (deje vacío para ${:.2}):",
+                    esperado
+                );
+                let monto = match leer_entrada() {
+                    monto if monto.is_empty() => Ok(esperado),
+                    monto => monto.parse::<f64>(),
+                };
+
+                let resultado = match (id, monto) {
+                    (Ok(id), Ok(monto)) => {
+                        gestor_productos.registrar_nota_credito(id, &numero, monto)
+                    }
+                    _ => Err("Número de devolución o monto no válidos".to_string()),
+                };
+                match resultado {
+                    Ok(()) => println!("Nota de crédito registrada"),
+                    Err(err) => println!("Error al registrar la nota de crédito: {}", err),
+                }
+            }
+            "4" => {
+                if gestor_productos.devoluciones_proveedor.is_empty() {
+                    println!("No hay devoluciones a proveedor");
+                }
+                for devolucion in &gestor_productos.devoluciones_proveedor {
+                    devolucion.mostrar();
+                }
+                let pendiente: f64 = gestor_productos
+                    .devoluciones_proveedor
+                    .iter()
+                    .filter(|d| matches!(d.estado, EstadoCredito::Pendiente))
+                    .map(|d| d.credito_esperado())
+                    .sum();
+                println!("Total pendiente de acreditar: ${:.2}", pendiente);
+            }
+            "5" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,206 @@
+use crate::kits::{pedir_componentes, sumar_componentes};
+use crate::movimientos::TipoMovimiento;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+// Orden que consume componentes y produce existencias de un producto terminado
+#[derive(Serialize, Deserialize)]
+pub struct OrdenEnsamble {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub producto: String,
+    pub cantidad: f64,
+    pub consumos: Vec<(String, f64)>,
+    pub costo_unitario: f64,
+}
+
+// Implementación de métodos de ensamble para GestorProductos
+impl GestorProductos {
+    pub fn definir_receta(
+        &mut self,
+        nombre: &str,
+        receta: Vec<(String, f64)>,
+    ) -> Result<(), String> {
+        match self.productos.get(nombre) {
+            Some(producto) if !producto.componentes.is_empty() => {
+                return Err("Los kits no se ensamblan, se venden por componentes".to_string());
+            }
+            Some(_) => {}
+            None => return Err("Producto no encontrado".to_string()),
+        }
+        if receta.is_empty() {
+            return Err("La receta debe tener al menos un componente".to_string());
+        }
+        for (componente, cantidad) in &receta {
+            if componente == nombre {
+                return Err("El producto no puede ser componente de sí mismo".to_string());
+            }
+            match self.productos.get(componente) {
+                Some(producto) if !producto.componentes.is_empty() => {
+                    return Err(format!(
+                        "{} es un kit y no puede ser componente",
+                        componente
+                    ));
+                }
+                Some(_) if *cantidad <= 0.0 => {
+                    return Err(format!("Cantidad no válida para {}", componente));
+                }
+                Some(_) => {}
+                None => return Err(format!("Componente no encontrado: {}", componente)),
+            }
+        }
+
+        if let Some(producto) = self.productos.get_mut(nombre) {
+            producto.receta = receta;
+        }
+        Ok(())
+    }
+
+    // Costo de una unidad ensamblada según el costo actual de sus componentes
+    pub fn costo_receta(&self, nombre: &str) -> f64 {
+        self.productos.get(nombre).map_or(0.0, |producto| {
+            producto
+                .receta
+                .iter()
+                .map(|(componente, cantidad)| {
+                    self.productos.get(componente).map_or(0.0, |c| c.costo) * cantidad
+                })
+                .sum()
+        })
+    }
+
+    // Consume los componentes y agrega el producto terminado en una sola operación
+    pub fn ensamblar(&mut self, nombre: &str, cantidad: f64) -> Result<u32, String> {
+        if cantidad <= 0.0 {
+            return Err("Cantidad no válida".to_string());
+        }
+        let receta = match self.productos.get(nombre) {
+            Some(producto) if !producto.receta.is_empty() => producto.receta.clone(),
+            Some(_) => return Err("El producto no tiene receta de ensamble".to_string()),
+            None => return Err("Producto no encontrado".to_string()),
+        };
+
+        let costo_unitario = self.costo_receta(nombre);
+        let requerimientos = sumar_componentes(&receta);
+        self.consumir_componentes(&requerimientos, cantidad, TipoMovimiento::ConsumoEnsamble)?;
+        self.entrada_producto(nombre, cantidad, costo_unitario, TipoMovimiento::Ensamble)?;
+
+        let id = self.ordenes_ensamble.len() as u32 + 1;
+        let mut consumos: Vec<(String, f64)> = requerimientos
+            .into_iter()
+            .map(|(componente, por_unidad)| (componente, por_unidad * cantidad))
+            .collect();
+        consumos.sort_by(|a, b| a.0.cmp(&b.0));
+        self.ordenes_ensamble.push(OrdenEnsamble {
+            id,
+            fecha: Local::now(),
+            producto: nombre.to_string(),
+            cantidad,
+            consumos,
+            costo_unitario,
+        });
+        Ok(id)
+    }
+}
+
+pub fn menu_ensamble(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Ensamble");
+        println!("--------");
+        println!("1. Definir Receta");
+        println!("2. Ver Receta");
+        println!("3. Ensamblar Producto");
+        println!("4. Historial de Órdenes de Ensamble");
+        println!("5. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el nombre o SKU del producto terminado:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+
+                println!("Ingrese los componentes por unidad, uno por línea con el formato");
+                println!("producto: cantidad (por ejemplo Tabla: 4)");
+                println!("Deje una línea vacía para terminar:");
+                let receta = pedir_componentes(gestor_productos);
+
+                match gestor_productos.definir_receta(&nombre, receta) {
+                    Ok(()) => println!("Receta guardada exitosamente"),
+                    Err(err) => println!("Error al guardar la receta: {}", err),
+                }
+            }
+            "2" => {
+                println!("Ingrese el nombre o SKU del producto terminado:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+                match gestor_productos.buscar_producto(&nombre) {
+                    Some(producto) if !producto.receta.is_empty() => {
+                        println!("Receta de {}:", producto.nombre);
+                        for (componente, cantidad) in &producto.receta {
+                            println!("  {} x {}", cantidad, componente);
+                        }
+                        println!(
+                            "Costo por unidad: ${:.2}",
+                            gestor_productos.costo_receta(&nombre)
+                        );
+                    }
+                    Some(_) => println!("El producto no tiene receta de ensamble"),
+                    None => println!("Producto no encontrado"),
+                }
+            }
+            "3" => {
+                println!("Ingrese el nombre o SKU del producto a ensamblar:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+
+                println!("Ingrese la cantidad a ensamblar:");
+                let cantidad: f64 = match leer_entrada().parse() {
+                    Ok(cantidad) => cantidad,
+                    Err(_) => {
+                        println!("Cantidad no válida");
+                        pausar();
+                        continue;
+                    }
+                };
+
+                match gestor_productos.ensamblar(&nombre, cantidad) {
+                    Ok(id) => println!("Orden de ensamble #{} realizada con éxito!", id),
+                    Err(err) => println!("{}", err),
+                }
+            }
+            "4" => {
+                if gestor_productos.ordenes_ensamble.is_empty() {
+                    println!("No hay órdenes de ensamble");
+                }
+                for orden in &gestor_productos.ordenes_ensamble {
+                    println!(
+                        "#{} - {} - {} x {} - Costo unitario: ${:.2}",
+                        orden.id,
+                        orden.fecha.format("%Y-%m-%d %H:%M"),
+                        orden.cantidad,
+                        orden.producto,
+                        orden.costo_unitario
+                    );
+                    for (componente, cantidad) in &orden.consumos {
+                        println!("    consumido: {} x {}", cantidad, componente);
+                    }
+                }
+            }
+            "5" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,310 @@
+use crate::categorias::pedir_categoria;
+use crate::codigos_barras::{escapar_xml, generar_svg, validar_codigo, TipoCodigo};
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos, Producto};
+
+// Puntos por milímetro de una impresora térmica de 203 dpi
+const PUNTOS_POR_MM: f64 = 8.0;
+
+// Distribución de las etiquetas en la hoja o tamaño de la etiqueta térmica
+pub struct FormatoEtiqueta {
+    pub nombre: String,
+    pub columnas: usize,
+    pub ancho_mm: f64,
+    pub alto_mm: f64,
+}
+
+pub fn formatos_predefinidos() -> Vec<FormatoEtiqueta> {
+    vec![
+        FormatoEtiqueta {
+            nombre: "Hoja A4 3x8 (70 x 37 mm)".to_string(),
+            columnas: 3,
+            ancho_mm: 70.0,
+            alto_mm: 37.0,
+        },
+        FormatoEtiqueta {
+            nombre: "Hoja A4 2x5 (105 x 57 mm)".to_string(),
+            columnas: 2,
+            ancho_mm: 105.0,
+            alto_mm: 57.0,
+        },
+        FormatoEtiqueta {
+            nombre: "Térmica 50 x 30 mm".to_string(),
+            columnas: 1,
+            ancho_mm: 50.0,
+            alto_mm: 30.0,
+        },
+    ]
+}
+
+// Precio por unidad de medida cuando el producto tiene contenido neto, por ejemplo $/L
+pub fn precio_unitario(producto: &Producto) -> Option<String> {
+    producto
+        .contenido
+        .as_ref()
+        .filter(|(cantidad, _)| *cantidad > 0.0)
+        .map(|(cantidad, unidad)| format!("${:.2}/{}", producto.precio / cantidad, unidad))
+}
+
+pub fn generar_html(productos: &[&Producto], formato: &FormatoEtiqueta) -> String {
+    let mut html = format!(
+        r#"<html>
+<head>
+    <meta charset="utf-8">
+    <title>Etiquetas</title>
+    <style>
+        @page {{ size: A4; margin: 10mm; }}
+        body {{ font-family: Arial, sans-serif; margin: 0; }}
+        .hoja {{ display: grid; grid-template-columns: repeat({}, {}mm); }}
+        .etiqueta {{
+            width: {}mm;
+            height: {}mm;
+            box-sizing: border-box;
+            padding: 2mm;
+            border: 1px dashed #ccc;
+            overflow: hidden;
+            page-break-inside: avoid;
+        }}
+        .nombre {{ font-weight: bold; font-size: 11pt; }}
+        .precio {{ font-size: 18pt; font-weight: bold; }}
+        .unitario {{ font-size: 8pt; color: #555; }}
+        .etiqueta svg {{ width: 100%; height: 40%; }}
+    </style>
+</head>
+<body>
+<div class="hoja">
+"#,
+        formato.columnas, formato.ancho_mm, formato.ancho_mm, formato.alto_mm
+    );
+
+    for producto in productos {
+        html.push_str("<div class=\"etiqueta\">\n");
+        html.push_str(&format!(
+            "<div class=\"nombre\">{}</div>\n",
+            escapar_xml(&producto.nombre)
+        ));
+        html.push_str(&format!(
+            "<div class=\"precio\">${:.2}</div>\n",
+            producto.precio
+        ));
+        if let Some(unitario) = precio_unitario(producto) {
+            html.push_str(&format!("<div class=\"unitario\">{}</div>\n", unitario));
+        }
+        if let Some(svg) = producto
+            .codigos_barras
+            .first()
+            .and_then(|codigo| generar_svg(codigo).ok())
+        {
+            // El código se estira para ocupar el ancho de la etiqueta
+            html.push_str(&svg.replacen("<svg ", "<svg preserveAspectRatio=\"none\" ", 1));
+        }
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+pub fn generar_zpl(productos: &[&Producto], formato: &FormatoEtiqueta) -> String {
+    let ancho = (formato.ancho_mm * PUNTOS_POR_MM) as u32;
+    let alto = (formato.alto_mm * PUNTOS_POR_MM) as u32;
+    let mut zpl = String::new();
+
+    for producto in productos {
+        zpl.push_str("^XA\n^CI28\n");
+        zpl.push_str(&format!("^PW{}\n^LL{}\n", ancho, alto));
+        zpl.push_str(&format!(
+            "^FO16,16^A0N,28,28^FB{},1,0,L^FD{}^FS\n",
+            ancho.saturating_sub(32),
+            texto_zpl(&producto.nombre)
+        ));
+        zpl.push_str(&format!(
+            "^FO16,50^A0N,40,40^FD${:.2}^FS\n",
+            producto.precio
+        ));
+        if let Some(unitario) = precio_unitario(producto) {
+            zpl.push_str(&format!("^FO16,94^A0N,20,20^FD{}^FS\n", unitario));
+        }
+        if let Some(codigo) = producto.codigos_barras.first() {
+            let alto_barras = alto.saturating_sub(150).max(30);
+            match validar_codigo(codigo) {
+                // ^BE calcula el dígito verificador, por eso se envían solo los primeros 12 dígitos
+                Ok(TipoCodigo::Ean13) => zpl.push_str(&format!(
+                    "^FO16,120^BY2^BEN,{},Y,N^FD{}^FS\n",
+                    alto_barras,
+                    &codigo[..12]
+                )),
+                Ok(_) => zpl.push_str(&format!(
+                    "^FO16,120^BY2^BCN,{},Y,N,N^FD{}^FS\n",
+                    alto_barras,
+                    texto_zpl(codigo)
+                )),
+                Err(_) => {}
+            }
+        }
+        zpl.push_str("^XZ\n");
+    }
+    zpl
+}
+
+// Los caracteres ^ y ~ son comandos en ZPL y no pueden aparecer en los datos
+fn texto_zpl(texto: &str) -> String {
+    texto.replace(['^', '~'], " ")
+}
+
+fn pedir_formato() -> Option<FormatoEtiqueta> {
+    let mut formatos = formatos_predefinidos();
+    println!("Seleccione el formato de etiqueta:");
+    for (i, formato) in formatos.iter().enumerate() {
+        println!("{}. {}", i + 1, formato.nombre);
+    }
+    println!("{}. Personalizado", formatos.len() + 1);
+
+    let opcion: usize = leer_entrada().parse().ok()?;
+    if opcion >= 1 && opcion <= formatos.len() {
+        return Some(formatos.swap_remove(opcion - 1));
+    }
+    if opcion != formatos.len() + 1 {
+        return None;
+    }
+
+    println!("Ingrese el número de columnas por hoja:");
+    let columnas: usize = leer_entrada().parse().ok().filter(|c| *c > 0)?;
+    println!("Ingrese el ancho de la etiqueta en mm:");
+    let ancho_mm: f64 = leer_entrada().parse().ok()?;
+    println!("Ingrese el alto de la etiqueta en mm:");
+    let alto_mm: f64 = leer_entrada().parse().ok()?;
+    Some(FormatoEtiqueta {
+        nombre: "Personalizado".to_string(),
+        columnas,
+        ancho_mm,
+        alto_mm,
+    })
+}
+
+// Pide los productos a etiquetar y cuántas copias de cada uno
+fn pedir_productos(gestor_productos: &GestorProductos) -> Vec<String> {
+    println!("1. Seleccionar productos");
+    println!("2. Todos los productos de una categoría");
+    let nombres: Vec<String> = match leer_entrada().as_str() {
+        "1" => {
+            println!("Ingrese el nombre, SKU o código de cada producto, uno por línea.");
+            println!("Deje una línea vacía para terminar:");
+            let mut nombres = Vec::new();
+            loop {
+                let entrada = leer_entrada();
+                if entrada.is_empty() {
+                    break;
+                }
+                match gestor_productos.resolver_producto(&entrada) {
+                    Some(nombre) => nombres.push(nombre),
+                    None => println!("Producto no encontrado, se ignora la línea"),
+                }
+            }
+            nombres
+        }
+        "2" => match pedir_categoria(gestor_productos) {
+            Some(categoria) => gestor_productos
+                .productos_en_categoria(categoria)
+                .iter()
+                .map(|p| p.nombre.clone())
+                .collect(),
+            None => Vec::new(),
+        },
+        _ => {
+            println!("Opción no válida");
+            Vec::new()
+        }
+    };
+
+    println!("Ingrese el número de copias por producto (deje vacío para 1):");
+    let copias: usize = leer_entrada().parse().unwrap_or(1).max(1);
+    nombres
+        .into_iter()
+        .flat_map(|nombre| std::iter::repeat_n(nombre, copias))
+        .collect()
+}
+
+pub fn menu_etiquetas(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Etiquetas");
+        println!("---------");
+        println!("1. Definir Contenido Neto de un Producto");
+        println!("2. Generar Hoja de Etiquetas (HTML)");
+        println!("3. Generar Etiquetas Térmicas (ZPL)");
+        println!("4. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el nombre, SKU o código de barras del producto:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+                println!("Ingrese el contenido neto y su unidad (por ejemplo 0.5 L):");
+                let contenido = leer_entrada();
+                let contenido = contenido
+                    .split_once(' ')
+                    .and_then(|(cantidad, unidad)| {
+                        Some((cantidad.parse::<f64>().ok()?, unidad.trim().to_string()))
+                    })
+                    .filter(|(cantidad, unidad)| *cantidad > 0.0 && !unidad.is_empty());
+                match (gestor_productos.productos.get_mut(&nombre), contenido) {
+                    (Some(producto), Some(contenido)) => {
+                        producto.contenido = Some(contenido);
+                        println!("Contenido neto guardado exitosamente");
+                    }
+                    (None, _) => println!("Producto no encontrado"),
+                    (_, None) => println!("Contenido no válido"),
+                }
+            }
+            opcion @ ("2" | "3") => {
+                let nombres = pedir_productos(gestor_productos);
+                let productos: Vec<&Producto> = nombres
+                    .iter()
+                    .filter_map(|nombre| gestor_productos.buscar_producto(nombre))
+                    .collect();
+                if productos.is_empty() {
+                    println!("No hay productos para etiquetar");
+                    pausar();
+                    continue;
+                }
+                let Some(formato) = pedir_formato() else {
+                    println!("Formato no válido");
+                    pausar();
+                    continue;
+                };
+
+                let (contenido, ruta_defecto) = if opcion == "2" {
+                    (generar_html(&productos, &formato), "etiquetas.html")
+                } else {
+                    (generar_zpl(&productos, &formato), "etiquetas.zpl")
+                };
+                if opcion == "3" {
+                    println!(
+                        "Puede indicar el dispositivo de la impresora, por ejemplo /dev/usb/lp0"
+                    );
+                }
+                println!(
+                    "Ingrese la ruta de salida (deje vacío para {}):",
+                    ruta_defecto
+                );
+                let ruta = match leer_entrada() {
+                    ruta if ruta.is_empty() => ruta_defecto.to_string(),
+                    ruta => ruta,
+                };
+                match std::fs::write(&ruta, contenido) {
+                    Ok(()) => println!("{} etiquetas enviadas a {}", productos.len(), ruta),
+                    Err(err) => println!("Error al escribir las etiquetas: {}", err),
+                }
+            }
+            "4" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,353 @@
+use crate::reportes::escribir_csv;
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, GestorProductos};
+use chrono::NaiveDate;
+use rust_xlsxwriter::{Format, Workbook};
+use serde_json::{json, Map, Value};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FormatoExportacion {
+    Csv,
+    Json,
+    Xlsx,
+}
+
+// Datos listos para exportar, una celda por columna en cada fila
+pub struct Tabla {
+    pub columnas: Vec<&'static str>,
+    pub filas: Vec<Vec<Value>>,
+}
+
+// Redondea montos a centavos para que no aparezcan errores de coma flotante
+fn dinero(monto: f64) -> Value {
+    json!((monto * 100.0).round() / 100.0)
+}
+
+fn texto_celda(celda: &Value) -> String {
+    match celda {
+        Value::Null => String::new(),
+        Value::String(texto) => texto.clone(),
+        otro => otro.to_string(),
+    }
+}
+
+impl Tabla {
+    // Conserva solo las columnas indicadas, en el orden indicado
+    pub fn seleccionar(&self, columnas: &[usize]) -> Tabla {
+        Tabla {
+            columnas: columnas.iter().map(|&c| self.columnas[c]).collect(),
+            filas: self
+                .filas
+                .iter()
+                .map(|fila| columnas.iter().map(|&c| fila[c].clone()).collect())
+                .collect(),
+        }
+    }
+
+    pub fn a_csv(&self) -> Result<String, String> {
+        let filas: Vec<Vec<String>> = self
+            .filas
+            .iter()
+            .map(|fila| fila.iter().map(texto_celda).collect())
+            .collect();
+        escribir_csv(&self.columnas, &filas)
+    }
+
+    // Un objeto por fila con las columnas como claves
+    pub fn objetos(&self) -> Vec<Value> {
+        self.filas
+            .iter()
+            .map(|fila| {
+                let objeto: Map<String, Value> = self
+                    .columnas
+                    .iter()
+                    .map(|c| c.to_string())
+                    .zip(fila.iter().cloned())
+                    .collect();
+                Value::Object(objeto)
+            })
+            .collect()
+    }
+
+    pub fn a_json(&self) -> String {
+        serde_json::to_string_pretty(&self.objetos()).unwrap_or_default()
+    }
+
+    // Libro de Excel con una sola hoja y los encabezados en negrita
+    pub fn guardar_xlsx(&self, ruta: &str, nombre_hoja: &str) -> Result<(), String> {
+        let mut libro = Workbook::new();
+        let hoja = libro.add_worksheet();
+        hoja.set_name(nombre_hoja).map_err(|err| err.to_string())?;
+        let negrita = Format::new().set_bold();
+        for (columna, encabezado) in self.columnas.iter().enumerate() {
+            hoja.write_string_with_format(0, columna as u16, *encabezado, &negrita)
+                .map_err(|err| err.to_string())?;
+        }
+        for (i, fila) in self.filas.iter().enumerate() {
+            let numero_fila = i as u32 + 1;
+            for (columna, celda) in fila.iter().enumerate() {
+                let columna = columna as u16;
+                let escrito = match celda {
+                    Value::Null => continue,
+                    Value::Number(numero) => {
+                        hoja.write_number(numero_fila, columna, numero.as_f64().unwrap_or(0.0))
+                    }
+                    Value::Bool(valor) => hoja.write_boolean(numero_fila, columna, *valor),
+                    otro => hoja.write_string(numero_fila, columna, texto_celda(otro)),
+                };
+                escrito.map_err(|err| err.to_string())?;
+            }
+        }
+        hoja.autofit();
+        libro.save(ruta).map_err(|err| err.to_string())
+    }
+
+    pub fn guardar(&self, formato: FormatoExportacion, ruta: &str) -> Result<(), String> {
+        match formato {
+            FormatoExportacion::Csv => {
+                std::fs::write(ruta, self.a_csv()?).map_err(|err| err.to_string())
+            }
+            FormatoExportacion::Json => {
+                std::fs::write(ruta, self.a_json()).map_err(|err| err.to_string())
+            }
+            FormatoExportacion::Xlsx => self.guardar_xlsx(ruta, "Datos"),
+        }
+    }
+}
+
+// Implementación de métodos de exportación para GestorProductos
+impl GestorProductos {
+    pub fn tabla_catalogo(&self) -> Tabla {
+        let mut productos: Vec<_> = self.productos.values().collect();
+        productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        Tabla {
+            columnas: vec![
+                "nombre",
+                "sku",
+                "descripcion",
+                "categoria",
+                "precio",
+                "costo",
+                "cantidad_minima",
+                "codigos_barras",
+                "producto_padre",
+                "atributos",
+                "kit",
+            ],
+            filas: productos
+                .into_iter()
+                .map(|p| {
+                    let atributos: Vec<String> = p
+                        .atributos
+                        .iter()
+                        .map(|(atributo, valor)| format!("{}: {}", atributo, valor))
+                        .collect();
+                    vec![
+                        json!(p.nombre),
+                        json!(p.sku),
+                        json!(p.descripcion),
+                        p.categoria
+                            .map_or(Value::Null, |c| json!(self.ruta_categoria(c))),
+                        dinero(p.precio),
+                        dinero(p.costo),
+                        json!(p.cantidad_minima),
+                        json!(p.codigos_barras.join(" ")),
+                        p.padre.as_ref().map_or(Value::Null, |padre| json!(padre)),
+                        json!(atributos.join(", ")),
+                        json!(!p.componentes.is_empty()),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    // Los kits no tienen existencia propia y no se incluyen
+    pub fn tabla_existencias(&self) -> Tabla {
+        let mut productos: Vec<_> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty())
+            .collect();
+        productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        Tabla {
+            columnas: vec![
+                "nombre",
+                "sku",
+                "cantidad",
+                "cantidad_minima",
+                "bajo_minimo",
+                "costo",
+                "valor",
+            ],
+            filas: productos
+                .into_iter()
+                .map(|p| {
+                    vec![
+                        json!(p.nombre),
+                        json!(p.sku),
+                        json!(p.cantidad_disponible),
+                        json!(p.cantidad_minima),
+                        json!(p.cantidad_disponible < p.cantidad_minima),
+                        dinero(p.costo),
+                        dinero(p.cantidad_disponible * p.costo),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    pub fn tabla_movimientos(&self, desde: NaiveDate, hasta: NaiveDate) -> Tabla {
+        Tabla {
+            columnas: vec![
+                "fecha",
+                "producto",
+                "tipo",
+                "cantidad",
+                "costo_anterior",
+                "costo_resultante",
+            ],
+            filas: self
+                .movimientos
+                .iter()
+                .filter(|m| (desde..=hasta).contains(&m.fecha.date_naive()))
+                .map(|m| {
+                    vec![
+                        json!(m.fecha.format("%Y-%m-%d %H:%M:%S").to_string()),
+                        json!(m.producto),
+                        json!(m.tipo.nombre()),
+                        json!(m.cantidad),
+                        dinero(m.costo_anterior),
+                        dinero(m.costo_resultante),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    // Una fila por línea vendida, con los datos de la venta repetidos
+    pub fn tabla_ventas(&self, desde: NaiveDate, hasta: NaiveDate) -> Tabla {
+        let mut filas = Vec::new();
+        for venta in self
+            .ventas
+            .iter()
+            .filter(|v| (desde..=hasta).contains(&v.fecha.date_naive()))
+        {
+            for linea in &venta.lineas {
+                filas.push(vec![
+                    json!(venta.id),
+                    json!(venta.fecha.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    json!(venta.usuario),
+                    venta.cliente.as_ref().map_or(Value::Null, |c| json!(c)),
+                    json!(linea.producto),
+                    json!(linea.cantidad),
+                    dinero(linea.precio_unitario),
+                    dinero(linea.descuento_promocion() + linea.descuento_manual()),
+                    dinero(linea.subtotal()),
+                    dinero(linea.costo_unitario),
+                    dinero(venta.total),
+                ]);
+            }
+        }
+        Tabla {
+            columnas: vec![
+                "venta",
+                "fecha",
+                "usuario",
+                "cliente",
+                "producto",
+                "cantidad",
+                "precio_unitario",
+                "descuento",
+                "subtotal",
+                "costo_unitario",
+                "total_venta",
+            ],
+            filas,
+        }
+    }
+}
+
+// Lista las columnas y devuelve las elegidas; todas si se deja vacío
+fn pedir_columnas(tabla: &Tabla) -> Option<Vec<usize>> {
+    for (i, columna) in tabla.columnas.iter().enumerate() {
+        println!("{}. {}", i + 1, columna);
+    }
+    println!("Ingrese los números de las columnas separados por comas (deje vacío para todas):");
+    let entrada = leer_entrada();
+    if entrada.is_empty() {
+        return Some((0..tabla.columnas.len()).collect());
+    }
+    let mut columnas = Vec::new();
+    for parte in entrada.split(',') {
+        match parte.trim().parse::<usize>() {
+            Ok(n) if (1..=tabla.columnas.len()).contains(&n) => columnas.push(n - 1),
+            _ => {
+                println!("Columna no válida: {}", parte.trim());
+                return None;
+            }
+        }
+    }
+    Some(columnas)
+}
+
+pub fn menu_exportacion(gestor_productos: &GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Exportar Datos");
+        println!("--------------");
+        println!("1. Catálogo de Productos");
+        println!("2. Existencias Actuales");
+        println!("3. Movimientos de Inventario");
+        println!("4. Ventas");
+        println!("5. Volver al Menú Principal");
+
+        let opcion = leer_entrada();
+        let tabla = match opcion.as_str() {
+            "1" => gestor_productos.tabla_catalogo(),
+            "2" => gestor_productos.tabla_existencias(),
+            "3" | "4" => {
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                if opcion == "3" {
+                    gestor_productos.tabla_movimientos(desde, hasta)
+                } else {
+                    gestor_productos.tabla_ventas(desde, hasta)
+                }
+            }
+            "5" => break,
+            _ => {
+                println!("Opción no válida");
+                pausar();
+                continue;
+            }
+        };
+
+        let Some(columnas) = pedir_columnas(&tabla) else {
+            pausar();
+            continue;
+        };
+        println!("Formato: 1. CSV  2. JSON  3. XLSX (deje vacío para CSV):");
+        let formato = match leer_entrada().as_str() {
+            "2" => FormatoExportacion::Json,
+            "3" => FormatoExportacion::Xlsx,
+            _ => FormatoExportacion::Csv,
+        };
+        println!("Ingrese el archivo de destino:");
+        let archivo = leer_entrada();
+        if archivo.is_empty() {
+            println!("No se exportó nada");
+            pausar();
+            continue;
+        }
+        let tabla = tabla.seleccionar(&columnas);
+        match tabla.guardar(formato, &archivo) {
+            Ok(()) => println!("{} filas exportadas a {}", tabla.filas.len(), archivo),
+            Err(err) => println!("Error al exportar: {}", err),
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,516 @@
+use crate::codigos_barras::{normalizar_codigo, validar_codigo};
+use crate::movimientos::TipoMovimiento;
+use crate::{generar_sku, leer_entrada, limpiar_consola, pausar, GestorProductos, Producto};
+use calamine::Reader;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Campo {
+    Nombre,
+    Descripcion,
+    Precio,
+    Cantidad,
+    CantidadMinima,
+    Categoria,
+    Sku,
+    Costo,
+    CodigoBarras,
+}
+
+impl Campo {
+    pub const TODOS: [Campo; 9] = [
+        Campo::Nombre,
+        Campo::Descripcion,
+        Campo::Precio,
+        Campo::Cantidad,
+        Campo::CantidadMinima,
+        Campo::Categoria,
+        Campo::Sku,
+        Campo::Costo,
+        Campo::CodigoBarras,
+    ];
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Campo::Nombre => "nombre",
+            Campo::Descripcion => "descripcion",
+            Campo::Precio => "precio",
+            Campo::Cantidad => "cantidad",
+            Campo::CantidadMinima => "cantidad_minima",
+            Campo::Categoria => "categoria",
+            Campo::Sku => "sku",
+            Campo::Costo => "costo",
+            Campo::CodigoBarras => "codigo_barras",
+        }
+    }
+
+    // Reconoce el encabezado de una columna por su nombre en español o en inglés
+    pub fn desde_encabezado(encabezado: &str) -> Option<Campo> {
+        let encabezado = encabezado.trim().to_lowercase().replace([' ', '-'], "_");
+        let campo = match encabezado.as_str() {
+            "nombre" | "name" | "producto" | "product" => Campo::Nombre,
+            "descripcion" | "descripción" | "description" => Campo::Descripcion,
+            "precio" | "price" => Campo::Precio,
+            "cantidad" | "cantidad_disponible" | "quantity" | "stock" => Campo::Cantidad,
+            "cantidad_minima" | "cantidad_mínima" | "minimo" | "mínimo" | "minimum" => {
+                Campo::CantidadMinima
+            }
+            "categoria" | "categoría" | "category" => Campo::Categoria,
+            "sku" => Campo::Sku,
+            "costo" | "cost" => Campo::Costo,
+            "codigo_barras" | "código_de_barras" | "codigo" | "barcode" | "ean" | "upc" => {
+                Campo::CodigoBarras
+            }
+            _ => return None,
+        };
+        Some(campo)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ModoExistente {
+    Omitir,
+    Actualizar,
+    Fallar,
+}
+
+#[derive(Default)]
+pub struct ResultadoImportacion {
+    pub creados: usize,
+    pub actualizados: usize,
+    pub omitidos: usize,
+    // Número de fila en el archivo y descripción del error
+    pub errores: Vec<(usize, String)>,
+    pub aplicado: bool,
+}
+
+// Valores de una fila ya validados, None si la columna no está mapeada o viene vacía
+#[derive(Default)]
+struct FilaProducto {
+    nombre: String,
+    descripcion: Option<String>,
+    precio: Option<f64>,
+    cantidad: Option<f64>,
+    cantidad_minima: Option<f64>,
+    categoria: Option<u32>,
+    sku: Option<String>,
+    costo: Option<f64>,
+    codigo_barras: Option<String>,
+}
+
+// Asigna cada columna al campo que reconoce su encabezado
+pub fn mapeo_automatico(encabezados: &[String]) -> Vec<(usize, Campo)> {
+    let mut mapeo: Vec<(usize, Campo)> = Vec::new();
+    for (columna, encabezado) in encabezados.iter().enumerate() {
+        if let Some(campo) = Campo::desde_encabezado(encabezado) {
+            if !mapeo.iter().any(|(_, c)| *c == campo) {
+                mapeo.push((columna, campo));
+            }
+        }
+    }
+    mapeo
+}
+
+// Lee un CSV y devuelve los encabezados y las filas
+pub fn leer_csv(ruta: &str) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let mut lector = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(ruta)
+        .map_err(|err| err.to_string())?;
+    let encabezados = lector
+        .headers()
+        .map_err(|err| err.to_string())?
+        .iter()
+        .map(str::to_string)
+        .collect();
+    let mut filas = Vec::new();
+    for registro in lector.records() {
+        let registro = registro.map_err(|err| err.to_string())?;
+        filas.push(registro.iter().map(str::to_string).collect());
+    }
+    Ok((encabezados, filas))
+}
+
+// Lee la primera hoja de un libro de Excel; la primera fila son los encabezados
+pub fn leer_xlsx(ruta: &str) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let mut libro = calamine::open_workbook_auto(ruta).map_err(|err| err.to_string())?;
+    let hoja = libro
+        .worksheet_range_at(0)
+        .ok_or("El libro no tiene hojas")?
+        .map_err(|err| err.to_string())?;
+    let mut filas = hoja.rows().map(|fila| {
+        fila.iter()
+            .map(|celda| celda.to_string().trim().to_string())
+            .collect()
+    });
+    let encabezados = filas.next().unwrap_or_default();
+    Ok((encabezados, filas.collect()))
+}
+
+// Elige el lector según la extensión del archivo
+pub fn leer_archivo(ruta: &str) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    if ruta.to_lowercase().ends_with(".xlsx") {
+        leer_xlsx(ruta)
+    } else {
+        leer_csv(ruta)
+    }
+}
+
+fn numero(valor: &str, campo: Campo) -> Result<f64, String> {
+    match valor.replace(',', ".").parse::<f64>() {
+        Ok(numero) if numero >= 0.0 => Ok(numero),
+        _ => Err(format!("{} no válido: {}", campo.nombre(), valor)),
+    }
+}
+
+// Implementación de métodos de importación para GestorProductos
+impl GestorProductos {
+    fn validar_fila(
+        &self,
+        fila: &[String],
+        mapeo: &[(usize, Campo)],
+    ) -> Result<FilaProducto, String> {
+        let mut producto = FilaProducto::default();
+        for (columna, campo) in mapeo {
+            let valor = fila.get(*columna).map_or("", |v| v.trim());
+            if valor.is_empty() {
+                continue;
+            }
+            match campo {
+                Campo::Nombre => producto.nombre = valor.to_string(),
+                Campo::Descripcion => producto.descripcion = Some(valor.to_string()),
+                Campo::Precio => producto.precio = Some(numero(valor, *campo)?),
+                Campo::Cantidad => producto.cantidad = Some(numero(valor, *campo)?),
+                Campo::CantidadMinima => producto.cantidad_minima = Some(numero(valor, *campo)?),
+                Campo::Costo => producto.costo = Some(numero(valor, *campo)?),
+                Campo::Categoria => match self.buscar_categoria(valor) {
+                    Some(categoria) => producto.categoria = Some(categoria),
+                    None => return Err(format!("Categoría no encontrada: {}", valor)),
+                },
+                Campo::Sku => producto.sku = Some(generar_sku(valor)),
+                Campo::CodigoBarras => {
+                    validar_codigo(valor)?;
+                    producto.codigo_barras = Some(normalizar_codigo(valor));
+                }
+            }
+        }
+        if producto.nombre.is_empty() {
+            return Err("La fila no tiene nombre".to_string());
+        }
+        if let Some(codigo) = &producto.codigo_barras {
+            if let Some(otro) = self.buscar_por_codigo(codigo) {
+                if otro != producto.nombre {
+                    return Err(format!("El código {} ya pertenece a {}", codigo, otro));
+                }
+            }
+        }
+        if let Some(sku) = &producto.sku {
+            if let Some(otro) = self
+                .productos
+                .values()
+                .find(|p| p.sku == *sku && p.nombre != producto.nombre)
+            {
+                return Err(format!("El SKU {} ya pertenece a {}", sku, otro.nombre));
+            }
+        }
+        if self
+            .productos
+            .get(&producto.nombre)
+            .is_some_and(|p| !p.componentes.is_empty())
+        {
+            return Err("Los kits no se pueden importar".to_string());
+        }
+        Ok(producto)
+    }
+
+    fn aplicar_fila(&mut self, fila: FilaProducto, usuario: &str) -> Result<(), String> {
+        let nombre = fila.nombre.clone();
+        if !self.productos.contains_key(&nombre) {
+            let cantidad_minima = fila
+                .cantidad_minima
+                .or_else(|| fila.categoria.and_then(|c| self.cantidad_minima_defecto(c)))
+                .unwrap_or(0.0);
+            let mut producto = Producto::new(
+                nombre.clone(),
+                fila.descripcion.unwrap_or_default(),
+                fila.precio.unwrap_or(0.0),
+                fila.cantidad.unwrap_or(0.0),
+                cantidad_minima,
+            );
+            producto.categoria = fila.categoria;
+            producto.costo = fila.costo.unwrap_or(0.0);
+            if let Some(sku) = fila.sku {
+                producto.sku = sku;
+            }
+            self.agregar_producto(nombre.clone(), producto);
+        } else {
+            if let Some(precio) = fila.precio {
+                if self
+                    .productos
+                    .get(&nombre)
+                    .is_some_and(|p| p.precio != precio)
+                {
+                    self.cambiar_precio(&nombre, precio, usuario)
+                        .map_err(|err| format!("No se cambió el precio: {}", err))?;
+                }
+            }
+            let producto = self
+                .productos
+                .get_mut(&nombre)
+                .ok_or_else(|| "Producto no encontrado".to_string())?;
+            if let Some(descripcion) = fila.descripcion {
+                producto.descripcion = descripcion;
+            }
+            if let Some(cantidad_minima) = fila.cantidad_minima {
+                producto.cantidad_minima = cantidad_minima;
+            }
+            if let Some(categoria) = fila.categoria {
+                producto.categoria = Some(categoria);
+            }
+            if let Some(sku) = fila.sku {
+                producto.sku = sku;
+            }
+            let costo_anterior = producto.costo;
+            if let Some(costo) = fila.costo {
+                producto.costo = costo;
+            }
+            if let Some(cantidad) = fila.cantidad {
+                let diferencia = cantidad - producto.cantidad_disponible;
+                producto.cantidad_disponible = cantidad;
+                self.registrar_movimiento(
+                    &nombre,
+                    TipoMovimiento::Importacion,
+                    diferencia,
+                    costo_anterior,
+                );
+            }
+        }
+        if let Some(codigo) = fila.codigo_barras {
+            if self.buscar_por_codigo(&codigo).as_deref() != Some(nombre.as_str()) {
+                self.agregar_codigo_barras(&nombre, &codigo)
+                    .map_err(|err| format!("No se agregó el código {}: {}", codigo, err))?;
+            }
+        }
+        Ok(())
+    }
+
+    // Valida todas las filas antes de aplicar. En modo Fallar un producto existente o cualquier
+    // error cancela toda la importación; en los otros modos se aplican solo las filas válidas
+    pub fn importar_productos(
+        &mut self,
+        filas: &[Vec<String>],
+        mapeo: &[(usize, Campo)],
+        modo: ModoExistente,
+        simular: bool,
+        usuario: &str,
+    ) -> ResultadoImportacion {
+        let mut resultado = ResultadoImportacion::default();
+        if !mapeo.iter().any(|(_, campo)| *campo == Campo::Nombre) {
+            resultado
+                .errores
+                .push((1, "Ninguna columna está asignada al nombre".to_string()));
+            return resultado;
+        }
+
+        let mut validas = Vec::new();
+        let mut vistos = HashSet::new();
+        // SKU y códigos de las filas que se van a aplicar, para no asignarlos dos veces
+        let mut skus_vistos = HashSet::new();
+        let mut codigos_vistos = HashSet::new();
+        for (i, fila) in filas.iter().enumerate() {
+            // La fila 1 es el encabezado
+            let numero_fila = i + 2;
+            if fila.iter().all(|v| v.trim().is_empty()) {
+                continue;
+            }
+            let producto = match self.validar_fila(fila, mapeo) {
+                Ok(producto) => producto,
+                Err(err) => {
+                    resultado.errores.push((numero_fila, err));
+                    continue;
+                }
+            };
+            if !vistos.insert(producto.nombre.clone()) {
+                resultado.errores.push((
+                    numero_fila,
+                    format!("{} está repetido en el archivo", producto.nombre),
+                ));
+                continue;
+            }
+            if let Some(sku) = producto
+                .sku
+                .as_ref()
+                .filter(|sku| skus_vistos.contains(*sku))
+            {
+                resultado.errores.push((
+                    numero_fila,
+                    format!("El SKU {} está repetido en el archivo", sku),
+                ));
+                continue;
+            }
+            if let Some(codigo) = producto
+                .codigo_barras
+                .as_ref()
+                .filter(|codigo| codigos_vistos.contains(*codigo))
+            {
+                resultado.errores.push((
+                    numero_fila,
+                    format!("El código {} está repetido en el archivo", codigo),
+                ));
+                continue;
+            }
+            if self.productos.contains_key(&producto.nombre) {
+                match modo {
+                    ModoExistente::Omitir => {
+                        resultado.omitidos += 1;
+                        continue;
+                    }
+                    ModoExistente::Fallar => {
+                        resultado
+                            .errores
+                            .push((numero_fila, format!("{} ya existe", producto.nombre)));
+                        continue;
+                    }
+                    ModoExistente::Actualizar => resultado.actualizados += 1,
+                }
+            } else {
+                resultado.creados += 1;
+            }
+            skus_vistos.extend(producto.sku.clone());
+            codigos_vistos.extend(producto.codigo_barras.clone());
+            validas.push((numero_fila, producto));
+        }
+
+        if simular || (modo == ModoExistente::Fallar && !resultado.errores.is_empty()) {
+            return resultado;
+        }
+        for (numero_fila, producto) in validas {
+            if let Err(err) = self.aplicar_fila(producto, usuario) {
+                resultado.errores.push((numero_fila, err));
+            }
+        }
+        resultado.aplicado = true;
+        resultado
+    }
+}
+
+fn mostrar_mapeo(encabezados: &[String], mapeo: &[(usize, Campo)]) {
+    for (columna, encabezado) in encabezados.iter().enumerate() {
+        let campo = mapeo
+            .iter()
+            .find(|(c, _)| *c == columna)
+            .map_or("(sin asignar)", |(_, campo)| campo.nombre());
+        println!("{}. {} -> {}", columna + 1, encabezado, campo);
+    }
+}
+
+// Permite cambiar el campo de cada columna hasta recibir una línea vacía
+fn editar_mapeo(encabezados: &[String], mapeo: &mut Vec<(usize, Campo)>) {
+    loop {
+        mostrar_mapeo(encabezados, mapeo);
+        println!("Campos:");
+        for (i, campo) in Campo::TODOS.iter().enumerate() {
+            println!("  {}. {}", i + 1, campo.nombre());
+        }
+        println!("Ingrese columna y campo (por ejemplo 2 3), columna 0 para quitarla, vacío para terminar:");
+        let entrada = leer_entrada();
+        if entrada.is_empty() {
+            break;
+        }
+        let partes: Vec<usize> = entrada
+            .split_whitespace()
+            .filter_map(|p| p.parse().ok())
+            .collect();
+        match partes.as_slice() {
+            [columna, 0] if (1..=encabezados.len()).contains(columna) => {
+                mapeo.retain(|(c, _)| *c != columna - 1);
+            }
+            [columna, campo]
+                if (1..=encabezados.len()).contains(columna)
+                    && (1..=Campo::TODOS.len()).contains(campo) =>
+            {
+                let campo = Campo::TODOS[campo - 1];
+                mapeo.retain(|(c, f)| *c != columna - 1 && *f != campo);
+                mapeo.push((columna - 1, campo));
+            }
+            _ => println!("Entrada no válida"),
+        }
+    }
+}
+
+pub fn pedir_modo_existente() -> ModoExistente {
+    println!(
+        "Si el producto ya existe: 1. Omitir  2. Actualizar  3. Fallar (deje vacío para omitir)"
+    );
+    match leer_entrada().as_str() {
+        "2" => ModoExistente::Actualizar,
+        "3" => ModoExistente::Fallar,
+        _ => ModoExistente::Omitir,
+    }
+}
+
+pub fn mostrar_resultado(resultado: &ResultadoImportacion) {
+    for (fila, error) in &resultado.errores {
+        println!("Fila {}: {}", fila, error);
+    }
+    println!(
+        "Nuevos: {} - Actualizados: {} - Omitidos: {} - Con errores: {}",
+        resultado.creados,
+        resultado.actualizados,
+        resultado.omitidos,
+        resultado.errores.len()
+    );
+    if resultado.aplicado {
+        println!("Importación aplicada");
+    } else {
+        println!("No se aplicó ningún cambio");
+    }
+}
+
+pub fn menu_importacion(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Importar Productos (CSV o XLSX)");
+        println!("-------------------------------");
+        println!("1. Simular Importación (no aplica cambios)");
+        println!("2. Importar");
+        println!("3. Volver al Menú Principal");
+
+        let opcion = leer_entrada();
+        let simular = match opcion.as_str() {
+            "1" => true,
+            "2" => false,
+            "3" => break,
+            _ => {
+                println!("Opción no válida");
+                pausar();
+                continue;
+            }
+        };
+
+        println!("Ingrese la ruta del archivo CSV o XLSX:");
+        let ruta = leer_entrada();
+        let (encabezados, filas) = match leer_archivo(&ruta) {
+            Ok(datos) => datos,
+            Err(err) => {
+                println!("Error al leer el archivo: {}", err);
+                pausar();
+                continue;
+            }
+        };
+        let mut mapeo = mapeo_automatico(&encabezados);
+        println!("Columnas encontradas:");
+        mostrar_mapeo(&encabezados, &mapeo);
+        println!("¿Desea cambiar la asignación de columnas? (s/n):");
+        if leer_entrada().to_lowercase() == "s" {
+            editar_mapeo(&encabezados, &mut mapeo);
+        }
+        let modo = pedir_modo_existente();
+
+        let resultado =
+            gestor_productos.importar_productos(&filas, &mapeo, modo, simular, usuario_actual);
+        mostrar_resultado(&resultado);
+        pausar();
+    }
+}
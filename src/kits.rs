@@ -0,0 +1,251 @@
+use crate::movimientos::TipoMovimiento;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos, Producto};
+use std::collections::HashMap;
+
+// Implementación de métodos de kits para GestorProductos
+impl GestorProductos {
+    pub fn crear_kit(
+        &mut self,
+        nombre: &str,
+        descripcion: &str,
+        precio: f64,
+        cantidad_minima: f64,
+        componentes: Vec<(String, f64)>,
+    ) -> Result<(), String> {
+        if nombre.is_empty() || self.productos.contains_key(nombre) {
+            return Err("El nombre del kit no es válido o ya existe".to_string());
+        }
+        if componentes.is_empty() {
+            return Err("El kit debe tener al menos un componente".to_string());
+        }
+        for (componente, cantidad) in &componentes {
+            match self.productos.get(componente) {
+                Some(producto) if !producto.componentes.is_empty() => {
+                    return Err(format!(
+                        "{} es un kit y no puede ser componente",
+                        componente
+                    ));
+                }
+                Some(_) if *cantidad <= 0.0 => {
+                    return Err(format!("Cantidad no válida para {}", componente));
+                }
+                Some(_) => {}
+                None => return Err(format!("Componente no encontrado: {}", componente)),
+            }
+        }
+
+        let mut kit = Producto::new(
+            nombre.to_string(),
+            descripcion.to_string(),
+            precio,
+            0.0,
+            cantidad_minima,
+        );
+        kit.componentes = componentes;
+        self.agregar_producto(nombre.to_string(), kit);
+        Ok(())
+    }
+
+    // Cantidad de cada componente necesaria para armar un kit, sumando componentes repetidos
+    fn requerimientos_kit(&self, nombre: &str) -> HashMap<String, f64> {
+        match self.productos.get(nombre) {
+            Some(kit) => sumar_componentes(&kit.componentes),
+            None => HashMap::new(),
+        }
+    }
+
+    // Descuenta los componentes para la cantidad indicada, o ninguno si alguno no alcanza
+    pub fn consumir_componentes(
+        &mut self,
+        requerimientos: &HashMap<String, f64>,
+        cantidad: f64,
+        tipo: TipoMovimiento,
+    ) -> Result<(), String> {
+        for (componente, por_unidad) in requerimientos {
+            let disponible = self
+                .productos
+                .get(componente)
+                .map_or(0.0, |p| p.cantidad_disponible);
+            if disponible < por_unidad * cantidad {
+                return Err(format!("Cantidad insuficiente de {}", componente));
+            }
+        }
+
+        for (componente, por_unidad) in requerimientos {
+            if let Some(producto) = self.productos.get_mut(componente) {
+                producto.cantidad_disponible -= por_unidad * cantidad;
+                let costo = producto.costo;
+                self.registrar_movimiento(componente, tipo, -por_unidad * cantidad, costo);
+            }
+        }
+        Ok(())
+    }
+
+    // Para un kit la disponibilidad se calcula con el inventario de sus componentes
+    pub fn disponibilidad(&self, nombre: &str) -> f64 {
+        match self.productos.get(nombre) {
+            Some(producto) if producto.componentes.is_empty() => producto.cantidad_disponible,
+            Some(_) => self
+                .requerimientos_kit(nombre)
+                .iter()
+                .map(|(componente, cantidad)| {
+                    let disponible = self
+                        .productos
+                        .get(componente)
+                        .map_or(0.0, |p| p.cantidad_disponible);
+                    (disponible / cantidad).floor()
+                })
+                .fold(f64::INFINITY, f64::min),
+            None => 0.0,
+        }
+    }
+
+    pub fn vender_kit(&mut self, nombre: &str, cantidad: f64) -> Result<(), String> {
+        let requerimientos = self.requerimientos_kit(nombre);
+        self.consumir_componentes(&requerimientos, cantidad, TipoMovimiento::Venta)?;
+        if let Some(kit) = self.productos.get_mut(nombre) {
+            kit.vendido += cantidad;
+        }
+        Ok(())
+    }
+}
+
+pub fn sumar_componentes(componentes: &[(String, f64)]) -> HashMap<String, f64> {
+    let mut requerimientos = HashMap::new();
+    for (componente, cantidad) in componentes {
+        *requerimientos.entry(componente.clone()).or_insert(0.0) += cantidad;
+    }
+    requerimientos
+}
+
+// Lee líneas con el formato "producto: cantidad" hasta recibir una línea vacía
+pub fn pedir_componentes(gestor_productos: &GestorProductos) -> Vec<(String, f64)> {
+    let mut componentes = Vec::new();
+    loop {
+        let linea = leer_entrada();
+        if linea.is_empty() {
+            break;
+        }
+        let componente = linea.rsplit_once(':').and_then(|(producto, cantidad)| {
+            let producto = gestor_productos
+                .resolver_producto(producto.trim())
+                .unwrap_or_else(|| producto.trim().to_string());
+            Some((producto, cantidad.trim().parse::<f64>().ok()?))
+        });
+        match componente {
+            Some(componente) => componentes.push(componente),
+            None => println!("Formato no válido, se ignora la línea"),
+        }
+    }
+    componentes
+}
+
+pub fn menu_kits(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Kits y Combos");
+        println!("-------------");
+        println!("1. Crear Kit");
+        println!("2. Ver Kit");
+        println!("3. Listar Kits");
+        println!("4. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el nombre del kit:");
+                let nombre = leer_entrada();
+
+                println!("Ingrese la descripción del kit:");
+                let descripcion = leer_entrada();
+
+                println!("Ingrese el precio del kit:");
+                let precio: f64 = match leer_entrada().parse() {
+                    Ok(precio) => precio,
+                    Err(_) => {
+                        println!("Precio no válido");
+                        pausar();
+                        continue;
+                    }
+                };
+
+                println!("Ingrese la cantidad mínima de kits disponibles:");
+                let cantidad_minima: f64 = match leer_entrada().parse() {
+                    Ok(cantidad) => cantidad,
+                    Err(_) => {
+                        println!("Cantidad mínima no válida");
+                        pausar();
+                        continue;
+                    }
+                };
+
+                println!("Ingrese los componentes uno por línea con el formato");
+                println!("producto: cantidad (por ejemplo Vino Tinto: 2)");
+                println!("Deje una línea vacía para terminar:");
+                let componentes = pedir_componentes(gestor_productos);
+
+                match gestor_productos.crear_kit(
+                    &nombre,
+                    &descripcion,
+                    precio,
+                    cantidad_minima,
+                    componentes,
+                ) {
+                    Ok(()) => println!("Kit creado exitosamente"),
+                    Err(err) => println!("Error al crear el kit: {}", err),
+                }
+            }
+            "2" => {
+                println!("Ingrese el nombre o SKU del kit:");
+                let nombre = leer_entrada();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
+                match gestor_productos.buscar_producto(&nombre) {
+                    Some(kit) if !kit.componentes.is_empty() => {
+                        println!("Kit: {} - ${}", kit.nombre, kit.precio);
+                        for (componente, cantidad) in &kit.componentes {
+                            println!(
+                                "  {} x {} (disponible: {})",
+                                cantidad,
+                                componente,
+                                gestor_productos.disponibilidad(componente)
+                            );
+                        }
+                        println!(
+                            "Kits disponibles: {}",
+                            gestor_productos.disponibilidad(&nombre)
+                        );
+                    }
+                    _ => println!("Kit no encontrado"),
+                }
+            }
+            "3" => {
+                let mut kits: Vec<&Producto> = gestor_productos
+                    .productos
+                    .values()
+                    .filter(|p| !p.componentes.is_empty())
+                    .collect();
+                kits.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+                if kits.is_empty() {
+                    println!("No hay kits registrados");
+                }
+                for kit in kits {
+                    println!(
+                        "{} - {} - ${} - Disponibles: {}",
+                        kit.sku,
+                        kit.nombre,
+                        kit.precio,
+                        gestor_productos.disponibilidad(&kit.nombre)
+                    );
+                }
+            }
+            "4" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
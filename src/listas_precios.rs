@@ -0,0 +1,280 @@
+use crate::ventas::LineaVenta;
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Lista de precios con nombre, por ejemplo Minorista, Mayorista o VIP
+#[derive(Serialize, Deserialize)]
+pub struct ListaPrecios {
+    pub nombre: String,
+    // Porcentaje de descuento sobre el precio base para productos sin precio en la lista
+    pub descuento_general: f64,
+    // Por producto, escalones (cantidad desde, precio) ordenados por cantidad
+    pub precios: HashMap<String, Vec<(f64, f64)>>,
+}
+
+impl ListaPrecios {
+    pub fn precio(&self, producto: &str, cantidad: f64, precio_base: f64) -> f64 {
+        let escalon = self
+            .precios
+            .get(producto)
+            .and_then(|escalones| escalones.iter().rev().find(|(desde, _)| *desde <= cantidad));
+        match escalon {
+            Some((_, precio)) => *precio,
+            None => precio_base * (1.0 - self.descuento_general / 100.0),
+        }
+    }
+}
+
+// Implementación de métodos de listas de precios para GestorProductos
+impl GestorProductos {
+    pub fn crear_lista_precios(
+        &mut self,
+        nombre: &str,
+        descuento_general: f64,
+    ) -> Result<(), String> {
+        if nombre.is_empty() {
+            return Err("La lista debe tener un nombre".to_string());
+        }
+        if self.listas_precios.contains_key(nombre) {
+            return Err("Ya existe una lista con ese nombre".to_string());
+        }
+        if !(0.0..=100.0).contains(&descuento_general) {
+            return Err("Porcentaje no válido".to_string());
+        }
+        self.listas_precios.insert(
+            nombre.to_string(),
+            ListaPrecios {
+                nombre: nombre.to_string(),
+                descuento_general,
+                precios: HashMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    // Define el precio de un producto a partir de una cantidad, reemplaza el escalón si ya existe
+    pub fn definir_precio_lista(
+        &mut self,
+        lista: &str,
+        producto: &str,
+        desde: f64,
+        precio: f64,
+    ) -> Result<(), String> {
+        if !self.productos.contains_key(producto) {
+            return Err("Producto no encontrado".to_string());
+        }
+        if desde <= 0.0 || precio < 0.0 {
+            return Err("Cantidad o precio no válidos".to_string());
+        }
+        let lista = self
+            .listas_precios
+            .get_mut(lista)
+            .ok_or_else(|| "Lista de precios no encontrada".to_string())?;
+        let escalones = lista.precios.entry(producto.to_string()).or_default();
+        escalones.retain(|(cantidad, _)| *cantidad != desde);
+        escalones.push((desde, precio));
+        escalones.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(())
+    }
+
+    pub fn usar_lista_general(&mut self, lista: Option<&str>) -> Result<(), String> {
+        if let Some(lista) = lista {
+            if !self.listas_precios.contains_key(lista) {
+                return Err("Lista de precios no encontrada".to_string());
+            }
+        }
+        self.configuracion.lista_precios_defecto = lista.map(str::to_string);
+        Ok(())
+    }
+
+    // Precio de venta según la lista del cliente, o la lista general, y la cantidad
+    pub fn precio_para(&self, nombre: &str, cantidad: f64, cliente: Option<&str>) -> Option<f64> {
+        let precio_base = self.productos.get(nombre)?.precio;
+        let lista = cliente
+            .and_then(|cliente| self.clientes.get(cliente))
+            .and_then(|cliente| cliente.lista.as_ref())
+            .or(self.configuracion.lista_precios_defecto.as_ref())
+            .and_then(|lista| self.listas_precios.get(lista));
+        Some(match lista {
+            Some(lista) => lista.precio(nombre, cantidad, precio_base),
+            None => precio_base,
+        })
+    }
+
+    pub fn resolver_precios(&self, lineas: &mut [LineaVenta], cliente: Option<&str>) {
+        for linea in lineas.iter_mut() {
+            if let Some(precio) = self.precio_para(&linea.producto, linea.cantidad, cliente) {
+                linea.precio_unitario = precio;
+            }
+        }
+    }
+}
+
+// Pide el nombre de una lista existente, None si se deja vacío o no existe
+fn pedir_lista(gestor_productos: &GestorProductos, mensaje: &str) -> Option<String> {
+    println!("{}", mensaje);
+    let nombre = leer_entrada();
+    if nombre.is_empty() {
+        return None;
+    }
+    let lista = gestor_productos
+        .listas_precios
+        .keys()
+        .find(|lista| lista.eq_ignore_ascii_case(&nombre))
+        .cloned();
+    if lista.is_none() {
+        println!("Lista de precios no encontrada");
+    }
+    lista
+}
+
+pub fn menu_listas_precios(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Listas de Precios y Clientes");
+        println!("----------------------------");
+        match &gestor_productos.configuracion.lista_precios_defecto {
+            Some(lista) => println!("Lista general: {}", lista),
+            None => println!("Lista general: precio base de cada producto"),
+        }
+        println!("1. Ver Listas de Precios");
+        println!("2. Crear Lista de Precios");
+        println!("3. Definir Precio de un Producto en una Lista");
+        println!("4. Elegir Lista General");
+        println!("5. Registrar Cliente");
+        println!("6. Ver Clientes");
+        println!("7. Consultar Precio");
+        println!("8. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                if gestor_productos.listas_precios.is_empty() {
+                    println!("No hay listas de precios");
+                }
+                let mut listas: Vec<_> = gestor_productos.listas_precios.values().collect();
+                listas.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+                for lista in listas {
+                    println!(
+                        "{} (descuento general: {}%)",
+                        lista.nombre, lista.descuento_general
+                    );
+                    let mut productos: Vec<_> = lista.precios.iter().collect();
+                    productos.sort_by(|a, b| a.0.cmp(b.0));
+                    for (producto, escalones) in productos {
+                        let escalones: Vec<String> = escalones
+                            .iter()
+                            .map(|(desde, precio)| format!("desde {}: ${:.2}", desde, precio))
+                            .collect();
+                        println!("  {} - {}", producto, escalones.join(", "));
+                    }
+                }
+            }
+            "2" => {
+                println!("Ingrese el nombre de la lista:");
+                let nombre = leer_entrada();
+                println!(
+                    "Ingrese el descuento general sobre el precio base en porcentaje (deje vacío para 0):"
+                );
+                let resultado = match leer_entrada() {
+                    descuento if descuento.is_empty() => Ok(0.0),
+                    descuento => descuento.parse::<f64>(),
+                }
+                .map_err(|_| "Porcentaje no válido".to_string())
+                .and_then(|descuento| gestor_productos.crear_lista_precios(&nombre, descuento));
+                match resultado {
+                    Ok(()) => println!("Lista de precios creada"),
+                    Err(err) => println!("Error al crear la lista: {}", err),
+                }
+            }
+            "3" => {
+                let Some(lista) = pedir_lista(gestor_productos, "Ingrese el nombre de la lista:")
+                else {
+                    pausar();
+                    continue;
+                };
+                println!("Ingrese el nombre, SKU o código de barras del producto:");
+                let entrada = leer_entrada();
+                let producto = gestor_productos
+                    .resolver_producto(&entrada)
+                    .unwrap_or(entrada);
+                println!("Ingrese la cantidad desde la que aplica el precio (1 para todas):");
+                let desde = leer_entrada().parse::<f64>();
+                println!("Ingrese el precio:");
+                let precio = leer_entrada().parse::<f64>();
+                let resultado = match (desde, precio) {
+                    (Ok(desde), Ok(precio)) => {
+                        gestor_productos.definir_precio_lista(&lista, &producto, desde, precio)
+                    }
+                    _ => Err("Cantidad o precio no válidos".to_string()),
+                };
+                match resultado {
+                    Ok(()) => println!("Precio definido"),
+                    Err(err) => println!("Error al definir el precio: {}", err),
+                }
+            }
+            "4" => {
+                let lista = pedir_lista(
+                    gestor_productos,
+                    "Ingrese la lista general (deje vacío para usar el precio base):",
+                );
+                match gestor_productos.usar_lista_general(lista.as_deref()) {
+                    Ok(()) => println!("Lista general actualizada"),
+                    Err(err) => println!("Error: {}", err),
+                }
+            }
+            "5" => {
+                println!("Ingrese el nombre del cliente:");
+                let nombre = leer_entrada();
+                let lista = pedir_lista(
+                    gestor_productos,
+                    "Ingrese la lista de precios del cliente (deje vacío para la general):",
+                );
+                match gestor_productos.registrar_cliente(&nombre, lista.as_deref()) {
+                    Ok(()) => println!("Cliente registrado"),
+                    Err(err) => println!("Error al registrar el cliente: {}", err),
+                }
+            }
+            "6" => {
+                if gestor_productos.clientes.is_empty() {
+                    println!("No hay clientes registrados");
+                }
+                let mut clientes: Vec<_> = gestor_productos.clientes.values().collect();
+                clientes.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+                for cliente in clientes {
+                    println!(
+                        "{} - Lista: {}",
+                        cliente.nombre,
+                        cliente.lista.as_deref().unwrap_or("general")
+                    );
+                }
+            }
+            "7" => {
+                println!("Ingrese el nombre, SKU o código de barras del producto:");
+                let entrada = leer_entrada();
+                let producto = gestor_productos
+                    .resolver_producto(&entrada)
+                    .unwrap_or(entrada);
+                println!("Ingrese la cantidad:");
+                let cantidad = leer_entrada().parse::<f64>().unwrap_or(1.0);
+                println!("Ingrese el cliente (deje vacío si no tiene):");
+                let cliente = gestor_productos.buscar_cliente(&leer_entrada());
+                match gestor_productos.precio_para(&producto, cantidad, cliente.as_deref()) {
+                    Some(precio) => println!(
+                        "Precio unitario: ${:.2} - Total: ${:.2}",
+                        precio,
+                        precio * cantidad
+                    ),
+                    None => println!("Producto no encontrado"),
+                }
+            }
+            "8" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
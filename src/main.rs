@@ -1,13 +1,75 @@
+mod analisis;
+mod api;
+mod caja;
+mod categorias;
+mod cli;
+mod clientes;
+mod codigos_barras;
+mod compras;
+mod configuracion;
+mod conteos;
+mod descuentos;
+mod devoluciones;
+mod devoluciones_proveedor;
+mod ensamble;
+mod etiquetas;
+mod exportacion;
+mod importacion;
+mod kits;
+mod listas_precios;
+mod movimientos;
+mod pagos;
+mod persistencia;
+mod pos;
+mod precios;
+mod promociones;
+mod pronosticos;
+mod reabastecimiento;
+mod reportes;
+mod reportes_pdf;
+mod variantes;
+mod ventas;
+
+use caja::SesionCaja;
+use categorias::Categoria;
+use chrono::NaiveDate;
+use clientes::Cliente;
+use compras::{Compra, OrdenCompra};
+use configuracion::Configuracion;
+use conteos::ConteoInventario;
+use devoluciones::Devolucion;
+use devoluciones_proveedor::DevolucionProveedor;
+use ensamble::OrdenEnsamble;
+use listas_precios::ListaPrecios;
+use movimientos::{Instantanea, Movimiento, TipoMovimiento};
+use persistencia::ARCHIVO_INVENTARIO;
+use precios::{CambioPrecio, CambioProgramado};
+use promociones::Promocion;
+use reabastecimiento::ParametrosReorden;
 use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use variantes::ProductoPadre;
+use ventas::Venta;
 use web_view::*;
 
+#[derive(Serialize, Deserialize)]
 struct Producto {
     nombre: String,
     descripcion: String,
     precio: f64,
     cantidad_disponible: f64,
     cantidad_minima: f64,
+    categoria: Option<u32>,
+    sku: String,
+    padre: Option<String>,
+    atributos: Vec<(String, String)>,
+    vendido: f64,
+    componentes: Vec<(String, f64)>,
+    receta: Vec<(String, f64)>,
+    costo: f64,
+    codigos_barras: Vec<String>,
+    contenido: Option<(f64, String)>,
 }
 
 // Implementación de métodos para Producto
@@ -20,24 +82,67 @@ impl Producto {
         cantidad_minima: f64,
     ) -> Producto {
         Producto {
+            sku: generar_sku(&nombre),
             nombre,
             descripcion,
             precio,
             cantidad_disponible,
             cantidad_minima,
+            categoria: None,
+            padre: None,
+            atributos: Vec::new(),
+            vendido: 0.0,
+            componentes: Vec::new(),
+            receta: Vec::new(),
+            costo: 0.0,
+            codigos_barras: Vec::new(),
+            contenido: None,
         }
     }
 
     fn mostrar_detalle(&self) {
         println!("Nombre: {}", self.nombre);
+        println!("SKU: {}", self.sku);
+        if !self.codigos_barras.is_empty() {
+            println!("Códigos de barras: {}", self.codigos_barras.join(", "));
+        }
+        if !self.atributos.is_empty() {
+            let atributos: Vec<String> = self
+                .atributos
+                .iter()
+                .map(|(atributo, valor)| format!("{}: {}", atributo, valor))
+                .collect();
+            println!("Variante: {}", atributos.join(", "));
+        }
         println!("Descripción: {}", self.descripcion);
         println!("Precio: ${}", self.precio);
+        println!("Costo: ${:.2}", self.costo);
         println!("Cantidad Disponible: {}", self.cantidad_disponible);
     }
 }
 
+#[derive(Serialize, Deserialize)]
 struct GestorProductos {
     productos: HashMap<String, Producto>,
+    categorias: HashMap<u32, Categoria>,
+    productos_padre: HashMap<String, ProductoPadre>,
+    ordenes_ensamble: Vec<OrdenEnsamble>,
+    ventas: Vec<Venta>,
+    sesiones_caja: Vec<SesionCaja>,
+    devoluciones: Vec<Devolucion>,
+    configuracion: Configuracion,
+    compras: Vec<Compra>,
+    devoluciones_proveedor: Vec<DevolucionProveedor>,
+    promociones: Vec<Promocion>,
+    listas_precios: HashMap<String, ListaPrecios>,
+    clientes: HashMap<String, Cliente>,
+    historial_precios: Vec<CambioPrecio>,
+    cambios_programados: Vec<CambioProgramado>,
+    reorden: HashMap<String, ParametrosReorden>,
+    ordenes_compra: Vec<OrdenCompra>,
+    movimientos: Vec<Movimiento>,
+    instantaneas: Vec<Instantanea>,
+    conteos: Vec<ConteoInventario>,
 }
 
 // Implementación de métodos para GestorProductos
@@ -45,20 +150,99 @@ impl GestorProductos {
     fn new() -> GestorProductos {
         GestorProductos {
             productos: HashMap::new(),
+            categorias: HashMap::new(),
+            productos_padre: HashMap::new(),
+            ordenes_ensamble: Vec::new(),
+            ventas: Vec::new(),
+            sesiones_caja: Vec::new(),
+            devoluciones: Vec::new(),
+            configuracion: Configuracion::new(),
+            compras: Vec::new(),
+            devoluciones_proveedor: Vec::new(),
+            promociones: Vec::new(),
+            listas_precios: HashMap::new(),
+            clientes: HashMap::new(),
+            historial_precios: Vec::new(),
+            cambios_programados: Vec::new(),
+            reorden: HashMap::new(),
+            ordenes_compra: Vec::new(),
+            movimientos: Vec::new(),
+            instantaneas: Vec::new(),
+            conteos: Vec::new(),
         }
     }
 
-    fn agregar_producto(&mut self, nombre: String, producto: Producto) {
-        self.productos.insert(nombre, producto);
+    fn agregar_producto(&mut self, nombre: String, mut producto: Producto) {
+        // Si el SKU ya lo usa otro producto se le agrega un sufijo numérico
+        let sku_base = producto.sku.clone();
+        let mut sufijo = 2;
+        while self
+            .productos
+            .values()
+            .any(|p| p.sku == producto.sku && p.nombre != nombre)
+        {
+            producto.sku = format!("{}-{}", sku_base, sufijo);
+            sufijo += 1;
+        }
+        let (anterior, costo_anterior) = self
+            .productos
+            .get(&nombre)
+            .map_or((0.0, 0.0), |p| (p.cantidad_disponible, p.costo));
+        let cantidad = producto.cantidad_disponible - anterior;
+        self.productos.insert(nombre.clone(), producto);
+        self.registrar_movimiento(&nombre, TipoMovimiento::Inicial, cantidad, costo_anterior);
     }
 
     fn buscar_producto(&self, nombre: &str) -> Option<&Producto> {
         self.productos.get(nombre)
     }
 
+    // Obtiene el nombre del producto a partir de su nombre, su SKU o un código de barras
+    fn resolver_producto(&self, entrada: &str) -> Option<String> {
+        if self.productos.contains_key(entrada) {
+            return Some(entrada.to_string());
+        }
+        self.productos
+            .values()
+            .find(|p| p.sku.eq_ignore_ascii_case(entrada))
+            .map(|p| p.nombre.clone())
+            .or_else(|| self.buscar_por_codigo(entrada))
+    }
+
     fn comprar_producto(&mut self, nombre: &str, cantidad: f64) -> Result<(), String> {
+        let costo = self.productos.get(nombre).map_or(0.0, |p| p.costo);
+        self.comprar_producto_con_costo(nombre, cantidad, costo)
+    }
+
+    fn comprar_producto_con_costo(
+        &mut self,
+        nombre: &str,
+        cantidad: f64,
+        costo_unitario: f64,
+    ) -> Result<(), String> {
+        self.entrada_producto(nombre, cantidad, costo_unitario, TipoMovimiento::Compra)
+    }
+
+    // El costo del producto se actualiza con el promedio ponderado de la existencia y la entrada
+    fn entrada_producto(
+        &mut self,
+        nombre: &str,
+        cantidad: f64,
+        costo_unitario: f64,
+        tipo: TipoMovimiento,
+    ) -> Result<(), String> {
         if let Some(producto) = self.productos.get_mut(nombre) {
+            if !producto.componentes.is_empty() {
+                return Err("Los kits no se compran, compre sus componentes".to_string());
+            }
+            let costo_anterior = producto.costo;
+            let existencia = producto.cantidad_disponible.max(0.0);
+            if existencia + cantidad > 0.0 {
+                producto.costo = (producto.costo * existencia + costo_unitario * cantidad)
+                    / (existencia + cantidad);
+            }
             producto.cantidad_disponible += cantidad;
+            self.registrar_movimiento(nombre, tipo, cantidad, costo_anterior);
             Ok(())
         } else {
             Err("Producto no encontrado".to_string())
@@ -66,9 +250,19 @@ impl GestorProductos {
     }
 
     fn vender_producto(&mut self, nombre: &str, cantidad: f64) -> Result<(), String> {
+        if self
+            .productos
+            .get(nombre)
+            .is_some_and(|p| !p.componentes.is_empty())
+        {
+            return self.vender_kit(nombre, cantidad);
+        }
         if let Some(producto) = self.productos.get_mut(nombre) {
             if producto.cantidad_disponible >= cantidad {
                 producto.cantidad_disponible -= cantidad;
+                producto.vendido += cantidad;
+                let costo = producto.costo;
+                self.registrar_movimiento(nombre, TipoMovimiento::Venta, -cantidad, costo);
                 Ok(())
             } else {
                 Err("Cantidad insuficiente en el inventario".to_string())
@@ -80,7 +274,7 @@ impl GestorProductos {
 
     fn verificar_cantidad_minima(&self, nombre: &str) -> bool {
         if let Some(producto) = self.productos.get(nombre) {
-            producto.cantidad_disponible <= producto.cantidad_minima
+            self.disponibilidad(nombre) <= producto.cantidad_minima
         } else {
             false
         }
@@ -219,6 +413,25 @@ impl GestorUsuarios {
         Ok(())
     }
 
+    // Nombre, correo y rol de cada usuario, sin la contraseña
+    fn listar_usuarios(&self) -> Result<Vec<(String, String, bool)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT nombre, email, es_administrador FROM usuarios ORDER BY email")?;
+        let usuarios = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect();
+        usuarios
+    }
+
+    fn existe_usuario(&self, email: &str) -> Result<bool> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT COUNT(*) FROM usuarios WHERE email = ?1")?;
+        let count: i64 = stmt.query_row([email], |row| row.get(0))?;
+        Ok(count > 0)
+    }
+
     fn es_administrador(&self, email: &str) -> bool {
         let mut stmt = self
             .conn
@@ -235,10 +448,63 @@ impl GestorUsuarios {
     }
 }
 
+// Genera un SKU en mayúsculas usando solo las letras y números del texto
+fn generar_sku(texto: &str) -> String {
+    texto
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
 fn limpiar_consola() {
     print!("{}[2J", 27 as char);
 }
 
+// Función para leer una línea de la entrada sin espacios al inicio ni al final
+fn leer_entrada() -> String {
+    let mut entrada = String::new();
+    std::io::stdin()
+        .read_line(&mut entrada)
+        .expect("Error al leer la entrada");
+    entrada.trim().to_string()
+}
+
+fn pausar() {
+    println!("Presione Enter para continuar...");
+    leer_entrada();
+}
+
+// Función para pedir una fecha con el formato AAAA-MM-DD
+fn pedir_fecha(mensaje: &str) -> Option<NaiveDate> {
+    println!("{}", mensaje);
+    let fecha = NaiveDate::parse_from_str(&leer_entrada(), "%Y-%m-%d").ok();
+    if fecha.is_none() {
+        println!("Fecha no válida");
+    }
+    fecha
+}
+
+fn pedir_numero(mensaje: &str) -> Option<f64> {
+    println!("{}", mensaje);
+    let numero = leer_entrada().parse::<f64>().ok();
+    if numero.is_none() {
+        println!("Número no válido");
+    }
+    numero
+}
+
+// Pide un producto por nombre, SKU o código de barras
+fn pedir_producto(gestor_productos: &GestorProductos, mensaje: &str) -> Option<String> {
+    println!("{}", mensaje);
+    let entrada = leer_entrada();
+    let producto = gestor_productos.resolver_producto(&entrada);
+    if producto.is_none() {
+        println!("Producto no encontrado");
+    }
+    producto
+}
+
 // Función para el inicio de sesión
 fn iniciar_sesion(gestor_usuarios: &GestorUsuarios) -> Option<String> {
     println!("Iniciar Sesión");
@@ -308,18 +574,39 @@ fn mostrar_ventana_emergente() {
 // Main que tiene los menus y logica general junto con el match para la seleccion del usuario
 fn main() {
     let conn = rusqlite::Connection::open("users.db").expect("Error al abrir la base de datos");
-    let mut gestor_productos = GestorProductos::new();
+    let mut gestor_productos = match GestorProductos::cargar(ARCHIVO_INVENTARIO) {
+        Ok(gestor_productos) => gestor_productos,
+        Err(err) => {
+            eprintln!("Error al cargar el inventario: {}", err);
+            std::process::exit(cli::SALIDA_ERROR);
+        }
+    };
     let gestor_usuarios = GestorUsuarios::new(conn);
 
+    // Con argumentos se ejecuta un solo comando sin el menú interactivo
+    let argumentos: Vec<String> = std::env::args().skip(1).collect();
+    if !argumentos.is_empty() {
+        std::process::exit(cli::ejecutar(
+            &argumentos,
+            &mut gestor_productos,
+            &gestor_usuarios,
+        ));
+    }
+
     let mut usuario_actual = None;
 
     while usuario_actual.is_none() {
         usuario_actual = iniciar_sesion(&gestor_usuarios)
     }
 
-    let es_administrador = gestor_usuarios.es_administrador(usuario_actual.as_ref().unwrap());
+    let mut es_administrador = gestor_usuarios.es_administrador(usuario_actual.as_ref().unwrap());
 
     loop {
+        // Los cambios de precio programados se activan al llegar su fecha
+        gestor_productos.aplicar_cambios_programados(chrono::Local::now().date_naive());
+        // Al cambiar el día se guardan las existencias de cierre de los días terminados
+        gestor_productos.tomar_instantaneas_pendientes(chrono::Local::now().date_naive());
+
         limpiar_consola();
         println!("Bienvenido a Chepe te Vende");
         println!("1. Agregar Producto");
@@ -327,10 +614,33 @@ fn main() {
         println!("3. Comprar Producto");
         println!("4. Vender Producto");
         println!("5. Gestionar Cuenta");
+        println!("10. Categorías");
+        println!("11. Productos con Variantes");
+        println!("12. Kits y Combos");
+        println!("13. Ensamble");
+        println!("14. Códigos de Barras");
+        println!("15. Etiquetas");
+        println!("16. Punto de Venta");
+        println!("17. Caja y Pagos");
+        println!("18. Devoluciones");
+        println!("20. Devoluciones a Proveedor");
+        println!("24. Reabastecimiento");
 
         if es_administrador {
             println!("6. Añadir Usuario");
             println!("7. Eliminar Usuario");
+            println!("19. Configuración");
+            println!("21. Promociones");
+            println!("22. Listas de Precios y Clientes");
+            println!("23. Precios");
+            println!("25. Pronóstico de Demanda");
+            println!("26. Reportes de Ventas");
+            println!("27. Análisis de Inventario");
+            println!("28. Inventario Histórico");
+            println!("29. Importar Productos (CSV o XLSX)");
+            println!("30. Exportar Datos");
+            println!("31. Conteo de Inventario");
+            println!("32. Reportes en PDF");
         }
 
         println!("8. Cerrar sesion");
@@ -352,6 +662,12 @@ fn main() {
                     .read_line(&mut nombre)
                     .expect("Error al leer la entrada");
                 let nombre = nombre.trim().to_string();
+                // Reemplazar el producto perdería su historial; el precio se cambia en Precios
+                if nombre.is_empty() || gestor_productos.productos.contains_key(&nombre) {
+                    println!("El nombre no es válido o el producto ya existe");
+                    pausar();
+                    continue;
+                }
 
                 println!("Ingrese la descripción del producto:");
                 let mut descripcion = String::new();
@@ -377,21 +693,43 @@ fn main() {
                     .parse()
                     .expect("Error al convertir la cantidad");
 
-                println!("Ingrese la cantidad mínima que puede haber de {}: ", nombre);
+                println!("Ingrese la categoría del producto (deje vacío si no tiene):");
+                let ruta_categoria = leer_entrada();
+                let categoria = gestor_productos.buscar_categoria(&ruta_categoria);
+                if !ruta_categoria.is_empty() && categoria.is_none() {
+                    println!("Categoría no encontrada, el producto quedará sin categoría");
+                }
+                let minima_defecto =
+                    categoria.and_then(|id| gestor_productos.cantidad_minima_defecto(id));
+
+                match minima_defecto {
+                    Some(minima) => println!(
+                        "Ingrese la cantidad mínima que puede haber de {} (vacío para usar {}): ",
+                        nombre, minima
+                    ),
+                    None => println!("Ingrese la cantidad mínima que puede haber de {}: ", nombre),
+                }
 
                 let mut cantidad_minima = String::new();
                 std::io::stdin()
                     .read_line(&mut cantidad_minima)
                     .expect("Error al leer la entrada");
-                let cantidad_minima: f64 = cantidad_minima
-                    .trim()
-                    .parse()
-                    .expect("Error al convertir la cantidad mínima");
+                let cantidad_minima: f64 = match (cantidad_minima.trim(), minima_defecto) {
+                    ("", Some(minima)) => minima,
+                    (cantidad_minima, _) => cantidad_minima
+                        .parse()
+                        .expect("Error al convertir la cantidad mínima"),
+                };
 
-                gestor_productos.agregar_producto(
+                let mut producto = Producto::new(
                     nombre.clone(),
-                    Producto::new(nombre, descripcion, precio, cantidad, cantidad_minima),
+                    descripcion,
+                    precio,
+                    cantidad,
+                    cantidad_minima,
                 );
+                producto.categoria = categoria;
+                gestor_productos.agregar_producto(nombre, producto);
                 println!("Producto agregado exitosamente!");
                 std::io::stdin()
                     .read_line(&mut input)
@@ -401,15 +739,27 @@ fn main() {
                 limpiar_consola();
                 println!("Consultando Producto");
                 println!("--------------------");
-                println!("Ingrese el nombre del producto a consultar:");
+                println!("Ingrese el nombre, SKU o código de barras del producto a consultar:");
                 let mut nombre = String::new();
                 std::io::stdin()
                     .read_line(&mut nombre)
                     .expect("Error al leer la entrada");
                 let nombre = nombre.trim().to_string();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
 
                 if let Some(producto) = gestor_productos.buscar_producto(&nombre) {
                     producto.mostrar_detalle();
+                    if !producto.componentes.is_empty() {
+                        println!(
+                            "Kits disponibles según componentes: {}",
+                            gestor_productos.disponibilidad(&nombre)
+                        );
+                    }
+                    if let Some(categoria) = producto.categoria {
+                        println!("Categoría: {}", gestor_productos.ruta_categoria(categoria));
+                    }
                 } else {
                     println!("Producto no encontrado");
                 }
@@ -421,12 +771,15 @@ fn main() {
                 limpiar_consola();
                 println!("Comprando Producto");
                 println!("-------------------");
-                println!("Ingrese el nombre del producto a comprar:");
+                println!("Ingrese el nombre, SKU o código de barras del producto a comprar:");
                 let mut nombre = String::new();
                 std::io::stdin()
                     .read_line(&mut nombre)
                     .expect("Error al leer la entrada");
                 let nombre = nombre.trim().to_string();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
 
                 println!("Ingrese la cantidad a comprar:");
                 let mut cantidad = String::new();
@@ -438,9 +791,31 @@ fn main() {
                     .parse()
                     .expect("Error al convertir la cantidad");
 
-                match gestor_productos.comprar_producto(&nombre, cantidad) {
-                    Ok(()) => {
-                        println!("Compra realizada con éxito!");
+                println!("Ingrese el costo unitario (deje vacío para mantener el actual):");
+                let costo = leer_entrada();
+                println!("Ingrese el proveedor:");
+                let proveedor = leer_entrada();
+                let resultado = if costo.is_empty() {
+                    Ok(None)
+                } else {
+                    costo
+                        .parse::<f64>()
+                        .map(Some)
+                        .map_err(|_| "Costo no válido".to_string())
+                }
+                .and_then(|costo| {
+                    gestor_productos.registrar_compra(
+                        &nombre,
+                        cantidad,
+                        costo,
+                        &proveedor,
+                        usuario_actual.as_ref().unwrap(),
+                    )
+                });
+
+                match resultado {
+                    Ok(id) => {
+                        println!("Compra #{} realizada con éxito!", id);
                     }
                     Err(err) => {
                         println!("{}", err);
@@ -457,12 +832,15 @@ fn main() {
                 limpiar_consola();
                 println!("Vendiendo Producto");
                 println!("-------------------");
-                println!("Ingrese el nombre del producto a vender:");
+                println!("Ingrese el nombre, SKU o código de barras del producto a vender:");
                 let mut nombre = String::new();
                 std::io::stdin()
                     .read_line(&mut nombre)
                     .expect("Error al leer la entrada");
                 let nombre = nombre.trim().to_string();
+                let nombre = gestor_productos
+                    .resolver_producto(&nombre)
+                    .unwrap_or(nombre);
 
                 println!("Ingrese la cantidad a vender:");
                 let mut cantidad = String::new();
@@ -474,9 +852,55 @@ fn main() {
                     .parse()
                     .expect("Error al convertir la cantidad");
 
-                match gestor_productos.vender_producto(&nombre, cantidad) {
-                    Ok(()) => {
-                        println!("Venta realizada con éxito!");
+                println!("Ingrese el cliente (deje vacío si no tiene):");
+                let cliente = leer_entrada();
+                let cliente = gestor_productos.buscar_cliente(&cliente).or_else(|| {
+                    if !cliente.is_empty() {
+                        println!("Cliente no encontrado, se usará la lista general");
+                    }
+                    None
+                });
+
+                let resultado = match gestor_productos.nueva_linea_venta(&nombre, cantidad) {
+                    Ok(_) if gestor_productos.disponibilidad(&nombre) < cantidad => {
+                        Err("Cantidad insuficiente en el inventario".to_string())
+                    }
+                    Ok(linea) => {
+                        let mut lineas = vec![linea];
+                        gestor_productos.resolver_precios(&mut lineas, cliente.as_deref());
+                        gestor_productos.aplicar_promociones(&mut lineas);
+                        if let Some((promocion, monto)) = &lineas[0].promocion {
+                            println!("Promoción {}: -${:.2}", promocion, monto);
+                        }
+                        let descuento = descuentos::pedir_descuento(
+                            &gestor_productos,
+                            ventas::total_venta(&lineas, None),
+                            es_administrador,
+                        );
+                        let total = ventas::total_venta(&lineas, descuento);
+                        println!("Total a pagar: ${:.2}", total);
+                        match pagos::pedir_pagos(total) {
+                            Some((pagos, cambio)) => {
+                                if cambio > 0.0 {
+                                    println!("Cambio: ${:.2}", cambio);
+                                }
+                                gestor_productos.registrar_venta(
+                                    lineas,
+                                    cliente.as_deref(),
+                                    descuento,
+                                    pagos,
+                                    usuario_actual.as_ref().unwrap(),
+                                    es_administrador,
+                                )
+                            }
+                            None => Err("Pago cancelado".to_string()),
+                        }
+                    }
+                    Err(err) => Err(err),
+                };
+                match resultado {
+                    Ok(id) => {
+                        println!("Venta #{} realizada con éxito!", id);
                     }
                     Err(err) => {
                         println!("{}", err);
@@ -557,18 +981,137 @@ fn main() {
                 }
             }
 
+            "10" => {
+                categorias::menu_categorias(&mut gestor_productos);
+            }
+
+            "11" => {
+                variantes::menu_variantes(&mut gestor_productos);
+            }
+
+            "12" => {
+                kits::menu_kits(&mut gestor_productos);
+            }
+
+            "13" => {
+                ensamble::menu_ensamble(&mut gestor_productos);
+            }
+
+            "14" => {
+                codigos_barras::menu_codigos_barras(&mut gestor_productos);
+            }
+
+            "15" => {
+                etiquetas::menu_etiquetas(&mut gestor_productos);
+            }
+
+            "16" => {
+                pos::menu_pos(
+                    &mut gestor_productos,
+                    usuario_actual.as_ref().unwrap(),
+                    es_administrador,
+                );
+            }
+
+            "17" => {
+                caja::menu_caja(&mut gestor_productos, usuario_actual.as_ref().unwrap());
+            }
+
+            "18" => {
+                devoluciones::menu_devoluciones(
+                    &mut gestor_productos,
+                    usuario_actual.as_ref().unwrap(),
+                );
+            }
+
+            "20" => {
+                devoluciones_proveedor::menu_devoluciones_proveedor(
+                    &mut gestor_productos,
+                    usuario_actual.as_ref().unwrap(),
+                );
+            }
+
+            "24" => {
+                reabastecimiento::menu_reabastecimiento(
+                    &mut gestor_productos,
+                    usuario_actual.as_ref().unwrap(),
+                );
+            }
+
+            "19" if es_administrador => {
+                configuracion::menu_configuracion(&mut gestor_productos);
+            }
+
+            "21" if es_administrador => {
+                promociones::menu_promociones(&mut gestor_productos);
+            }
+
+            "22" if es_administrador => {
+                listas_precios::menu_listas_precios(&mut gestor_productos);
+            }
+
+            "25" if es_administrador => {
+                pronosticos::menu_pronosticos(&mut gestor_productos);
+            }
+
+            "26" if es_administrador => {
+                reportes::menu_reportes(&gestor_productos);
+            }
+
+            "27" if es_administrador => {
+                analisis::menu_analisis(&gestor_productos);
+            }
+
+            "28" if es_administrador => {
+                movimientos::menu_movimientos(&gestor_productos);
+            }
+
+            "29" if es_administrador => {
+                importacion::menu_importacion(
+                    &mut gestor_productos,
+                    usuario_actual.as_ref().unwrap(),
+                );
+            }
+
+            "30" if es_administrador => {
+                exportacion::menu_exportacion(&gestor_productos);
+            }
+
+            "31" if es_administrador => {
+                conteos::menu_conteos(&mut gestor_productos, usuario_actual.as_ref().unwrap());
+            }
+
+            "32" if es_administrador => {
+                reportes_pdf::menu_reportes_pdf(&gestor_productos);
+            }
+
+            "23" if es_administrador => {
+                precios::menu_precios(&mut gestor_productos, usuario_actual.as_ref().unwrap());
+            }
+
             "8" => {
                 limpiar_consola();
+                if gestor_productos
+                    .sesion_abierta(usuario_actual.as_ref().unwrap())
+                    .is_some()
+                {
+                    println!("Aviso: su caja sigue abierta, recuerde cerrarla en Caja y Pagos.");
+                }
                 println!("Sesión cerrada.");
                 usuario_actual = None;
                 // Solicitar inicio de sesión nuevamente
                 while usuario_actual.is_none() {
                     usuario_actual = iniciar_sesion(&gestor_usuarios);
                 }
+                es_administrador =
+                    gestor_usuarios.es_administrador(usuario_actual.as_ref().unwrap());
             }
 
             "9" => {
                 println!("Saliendo...");
+                if let Err(err) = gestor_productos.guardar(ARCHIVO_INVENTARIO) {
+                    println!("Error al guardar el inventario: {}", err);
+                }
                 break;
             }
 
@@ -577,6 +1120,12 @@ fn main() {
             }
         }
 
+        // Se guarda después de cada opción para no perder datos si el programa se cierra
+        if let Err(err) = gestor_productos.guardar(ARCHIVO_INVENTARIO) {
+            println!("Error al guardar el inventario: {}", err);
+            pausar();
+        }
+
         for (nombre, _) in gestor_productos.productos.iter() {
             if gestor_productos.verificar_cantidad_minima(nombre) {
                 mostrar_ventana_emergente();
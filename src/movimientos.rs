@@ -0,0 +1,285 @@
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, GestorProductos};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TipoMovimiento {
+    Inicial,
+    Compra,
+    Venta,
+    Devolucion,
+    DevolucionProveedor,
+    ConsumoEnsamble,
+    Ensamble,
+    Importacion,
+    Conteo,
+}
+
+impl TipoMovimiento {
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            TipoMovimiento::Inicial => "Existencia inicial",
+            TipoMovimiento::Compra => "Compra",
+            TipoMovimiento::Venta => "Venta",
+            TipoMovimiento::Devolucion => "Devolución de cliente",
+            TipoMovimiento::DevolucionProveedor => "Devolución a proveedor",
+            TipoMovimiento::ConsumoEnsamble => "Consumo de ensamble",
+            TipoMovimiento::Ensamble => "Ensamble",
+            TipoMovimiento::Importacion => "Importación",
+            TipoMovimiento::Conteo => "Conteo físico",
+        }
+    }
+}
+
+// Cambio en la existencia de un producto, positivo si entra y negativo si sale
+#[derive(Serialize, Deserialize)]
+pub struct Movimiento {
+    pub fecha: DateTime<Local>,
+    pub producto: String,
+    pub tipo: TipoMovimiento,
+    pub cantidad: f64,
+    pub costo_anterior: f64,
+    pub costo_resultante: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExistenciaHistorica {
+    pub producto: String,
+    pub cantidad: f64,
+    pub costo: f64,
+}
+
+impl ExistenciaHistorica {
+    pub fn valor(&self) -> f64 {
+        self.cantidad * self.costo
+    }
+}
+
+// Existencias al cierre de un día, guardadas para no reconstruirlas cada vez
+#[derive(Serialize, Deserialize)]
+pub struct Instantanea {
+    pub fecha: NaiveDate,
+    pub tomada: DateTime<Local>,
+    pub existencias: Vec<ExistenciaHistorica>,
+}
+
+// Último instante del día en la zona horaria local
+pub fn fin_del_dia(fecha: NaiveDate) -> Option<DateTime<Local>> {
+    let fin = fecha.and_time(NaiveTime::from_hms_opt(23, 59, 59)?);
+    Local.from_local_datetime(&fin).earliest()
+}
+
+// Implementación de métodos de historial de existencias para GestorProductos
+impl GestorProductos {
+    // Se llama después de modificar la existencia, el costo resultante es el actual del producto
+    pub fn registrar_movimiento(
+        &mut self,
+        producto: &str,
+        tipo: TipoMovimiento,
+        cantidad: f64,
+        costo_anterior: f64,
+    ) {
+        if cantidad == 0.0 {
+            return;
+        }
+        let costo_resultante = self.productos.get(producto).map_or(0.0, |p| p.costo);
+        self.movimientos.push(Movimiento {
+            fecha: Local::now(),
+            producto: producto.to_string(),
+            tipo,
+            cantidad,
+            costo_anterior,
+            costo_resultante,
+        });
+    }
+
+    // Se parte de la existencia actual y se deshacen los movimientos posteriores al momento
+    pub fn inventario_en(&self, momento: DateTime<Local>) -> Vec<ExistenciaHistorica> {
+        let mut nombres: Vec<&String> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty())
+            .map(|p| &p.nombre)
+            .collect();
+        nombres.sort();
+        nombres
+            .into_iter()
+            .filter_map(|nombre| {
+                let producto = self.productos.get(nombre)?;
+                let posteriores: Vec<&Movimiento> = self
+                    .movimientos
+                    .iter()
+                    .filter(|m| m.producto == *nombre && m.fecha > momento)
+                    .collect();
+                let cantidad = producto.cantidad_disponible
+                    - posteriores.iter().map(|m| m.cantidad).sum::<f64>();
+                let costo = self
+                    .movimientos
+                    .iter()
+                    .rev()
+                    .find(|m| m.producto == *nombre && m.fecha <= momento)
+                    .map(|m| m.costo_resultante)
+                    .or_else(|| posteriores.first().map(|m| m.costo_anterior))
+                    .unwrap_or(producto.costo);
+                (cantidad.abs() > 0.0).then(|| ExistenciaHistorica {
+                    producto: nombre.clone(),
+                    cantidad,
+                    costo,
+                })
+            })
+            .collect()
+    }
+
+    // Usa la instantánea del día si existe, si no reconstruye desde los movimientos
+    pub fn inventario_al_cierre(&self, fecha: NaiveDate) -> Vec<ExistenciaHistorica> {
+        match self.instantaneas.iter().find(|i| i.fecha == fecha) {
+            Some(instantanea) => instantanea
+                .existencias
+                .iter()
+                .map(|e| ExistenciaHistorica {
+                    producto: e.producto.clone(),
+                    cantidad: e.cantidad,
+                    costo: e.costo,
+                })
+                .collect(),
+            None => fin_del_dia(fecha).map_or(Vec::new(), |fin| self.inventario_en(fin)),
+        }
+    }
+
+    // Guarda el cierre de cada día terminado desde la última instantánea o el primer movimiento
+    pub fn tomar_instantaneas_pendientes(&mut self, hoy: NaiveDate) -> usize {
+        let desde = match self.instantaneas.last() {
+            Some(ultima) => ultima.fecha + Duration::days(1),
+            None => match self.movimientos.first() {
+                Some(movimiento) => movimiento.fecha.date_naive(),
+                None => return 0,
+            },
+        };
+        let mut tomadas = 0;
+        let mut fecha = desde;
+        while fecha < hoy {
+            if let Some(fin) = fin_del_dia(fecha) {
+                let existencias = self.inventario_en(fin);
+                self.instantaneas.push(Instantanea {
+                    fecha,
+                    tomada: Local::now(),
+                    existencias,
+                });
+                tomadas += 1;
+            }
+            fecha += Duration::days(1);
+        }
+        tomadas
+    }
+}
+
+fn mostrar_existencias(existencias: &[ExistenciaHistorica]) {
+    if existencias.is_empty() {
+        println!("No había existencias");
+        return;
+    }
+    for existencia in existencias {
+        println!(
+            "{} - cantidad: {} - costo: ${:.2} - valor: ${:.2}",
+            existencia.producto,
+            existencia.cantidad,
+            existencia.costo,
+            existencia.valor()
+        );
+    }
+    println!(
+        "Valor total del inventario: ${:.2}",
+        existencias.iter().map(|e| e.valor()).sum::<f64>()
+    );
+}
+
+pub fn menu_movimientos(gestor_productos: &GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Inventario Histórico");
+        println!("--------------------");
+        println!("1. Existencias a una Fecha y Hora");
+        println!("2. Existencias al Cierre de un Día");
+        println!("3. Ver Instantáneas de Cierre");
+        println!("4. Movimientos de un Producto");
+        println!("5. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                let Some(fecha) = pedir_fecha("Ingrese la fecha (AAAA-MM-DD):") else {
+                    pausar();
+                    continue;
+                };
+                println!("Ingrese la hora (HH:MM):");
+                let momento = NaiveTime::parse_from_str(&leer_entrada(), "%H:%M")
+                    .ok()
+                    .and_then(|hora| Local.from_local_datetime(&fecha.and_time(hora)).earliest());
+                match momento {
+                    Some(momento) => {
+                        println!("Existencias al {}", momento.format("%Y-%m-%d %H:%M"));
+                        mostrar_existencias(&gestor_productos.inventario_en(momento));
+                    }
+                    None => println!("Hora no válida"),
+                }
+            }
+            "2" => {
+                let Some(fecha) = pedir_fecha("Ingrese la fecha (AAAA-MM-DD):") else {
+                    pausar();
+                    continue;
+                };
+                println!("Existencias al cierre del {}", fecha);
+                mostrar_existencias(&gestor_productos.inventario_al_cierre(fecha));
+            }
+            "3" => {
+                if gestor_productos.instantaneas.is_empty() {
+                    println!("No hay instantáneas de cierre");
+                }
+                for instantanea in &gestor_productos.instantaneas {
+                    println!(
+                        "{} - {} productos - valor: ${:.2} (tomada el {})",
+                        instantanea.fecha,
+                        instantanea.existencias.len(),
+                        instantanea
+                            .existencias
+                            .iter()
+                            .map(|e| e.valor())
+                            .sum::<f64>(),
+                        instantanea.tomada.format("%Y-%m-%d %H:%M")
+                    );
+                }
+            }
+            "4" => {
+                println!("Ingrese el nombre, SKU o código de barras del producto:");
+                let entrada = leer_entrada();
+                let Some(nombre) = gestor_productos.resolver_producto(&entrada) else {
+                    println!("Producto no encontrado");
+                    pausar();
+                    continue;
+                };
+                let mut saldo = 0.0;
+                for movimiento in gestor_productos
+                    .movimientos
+                    .iter()
+                    .filter(|m| m.producto == nombre)
+                {
+                    saldo += movimiento.cantidad;
+                    println!(
+                        "{} - {} - {:+} - saldo: {} - costo: ${:.2}",
+                        movimiento.fecha.format("%Y-%m-%d %H:%M"),
+                        movimiento.tipo.nombre(),
+                        movimiento.cantidad,
+                        saldo,
+                        movimiento.costo_resultante
+                    );
+                }
+            }
+            "5" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{leer_entrada, GestorProductos};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// Diferencia máxima aceptada al comparar montos de dinero
+pub const TOLERANCIA: f64 = 0.005;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum MetodoPago {
+    Efectivo,
+    Tarjeta,
+    Transferencia,
+    CreditoTienda,
+}
+
+impl MetodoPago {
+    pub const TODOS: [MetodoPago; 4] = [
+        MetodoPago::Efectivo,
+        MetodoPago::Tarjeta,
+        MetodoPago::Transferencia,
+        MetodoPago::CreditoTienda,
+    ];
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            MetodoPago::Efectivo => "Efectivo",
+            MetodoPago::Tarjeta => "Tarjeta",
+            MetodoPago::Transferencia => "Transferencia bancaria",
+            MetodoPago::CreditoTienda => "Crédito de tienda",
+        }
+    }
+
+    // Acepta el nombre en español o en inglés, sin distinguir mayúsculas
+    pub fn interpretar(nombre: &str) -> Option<MetodoPago> {
+        match nombre.to_lowercase().as_str() {
+            "efectivo" | "cash" => Some(MetodoPago::Efectivo),
+            "tarjeta" | "card" => Some(MetodoPago::Tarjeta),
+            "transferencia" | "transfer" => Some(MetodoPago::Transferencia),
+            "credito" | "crédito" | "credit" => Some(MetodoPago::CreditoTienda),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Pago {
+    pub metodo: MetodoPago,
+    pub monto: f64,
+}
+
+pub fn validar_pagos(total: f64, pagos: &[Pago]) -> Result<(), String> {
+    if pagos.iter().any(|p| p.monto <= 0.0) {
+        return Err("Los montos de los pagos deben ser mayores a cero".to_string());
+    }
+    let pagado: f64 = pagos.iter().map(|p| p.monto).sum();
+    if (pagado - total).abs() > TOLERANCIA {
+        return Err(format!(
+            "Los pagos suman ${:.2} pero el total es ${:.2}",
+            pagado, total
+        ));
+    }
+    Ok(())
+}
+
+// Pide uno o más pagos hasta cubrir el total, devuelve los pagos y el cambio en efectivo
+pub fn pedir_pagos(total: f64) -> Option<(Vec<Pago>, f64)> {
+    let mut pagos = Vec::new();
+    let mut cambio = 0.0;
+    let mut restante = total;
+
+    while restante > TOLERANCIA {
+        println!("Restante por pagar: ${:.2}", restante);
+        for (i, metodo) in MetodoPago::TODOS.iter().enumerate() {
+            println!("{}. {}", i + 1, metodo.nombre());
+        }
+        println!("Seleccione el método de pago (deje vacío para cancelar):");
+        let entrada = leer_entrada();
+        if entrada.is_empty() {
+            return None;
+        }
+        let metodo = match entrada.parse::<usize>() {
+            Ok(opcion) if (1..=MetodoPago::TODOS.len()).contains(&opcion) => {
+                MetodoPago::TODOS[opcion - 1]
+            }
+            _ => {
+                println!("Método de pago no válido");
+                continue;
+            }
+        };
+
+        println!("Ingrese el monto (deje vacío para ${:.2}):", restante);
+        let entrada = leer_entrada();
+        let mut monto = if entrada.is_empty() {
+            restante
+        } else {
+            match entrada.parse::<f64>() {
+                Ok(monto) if monto > 0.0 => monto,
+                _ => {
+                    println!("Monto no válido");
+                    continue;
+                }
+            }
+        };
+        if monto > restante + TOLERANCIA {
+            if metodo != MetodoPago::Efectivo {
+                println!("Solo el pago en efectivo puede exceder el total");
+                continue;
+            }
+            cambio = monto - restante;
+            monto = restante;
+        }
+
+        restante -= monto;
+        pagos.push(Pago { metodo, monto });
+    }
+    Some((pagos, cambio))
+}
+
+// Implementación de métodos de pagos para GestorProductos
+impl GestorProductos {
+    pub fn desglose_pagos(&self, desde: NaiveDate, hasta: NaiveDate) -> Vec<(MetodoPago, f64)> {
+        MetodoPago::TODOS
+            .iter()
+            .map(|metodo| {
+                let monto = self
+                    .ventas
+                    .iter()
+                    .filter(|v| (desde..=hasta).contains(&v.fecha.date_naive()))
+                    .flat_map(|v| v.pagos.iter())
+                    .filter(|p| p.metodo == *metodo)
+                    .map(|p| p.monto)
+                    .sum();
+                (*metodo, monto)
+            })
+            .collect()
+    }
+}
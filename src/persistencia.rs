@@ -0,0 +1,25 @@
+use crate::GestorProductos;
+use std::path::Path;
+
+// Productos, ventas y demás datos del inventario; los usuarios siguen en users.db
+pub const ARCHIVO_INVENTARIO: &str = "inventario.json";
+
+// Implementación de métodos de guardado para GestorProductos
+impl GestorProductos {
+    // Si el archivo todavía no existe se empieza con un inventario vacío
+    pub fn cargar(ruta: &str) -> Result<GestorProductos, String> {
+        if !Path::new(ruta).exists() {
+            return Ok(GestorProductos::new());
+        }
+        let contenido = std::fs::read_to_string(ruta).map_err(|err| err.to_string())?;
+        serde_json::from_str(&contenido).map_err(|err| format!("{} no es válido: {}", ruta, err))
+    }
+
+    // Escribe primero a un archivo temporal para no dejar el inventario a medias si algo falla
+    pub fn guardar(&self, ruta: &str) -> Result<(), String> {
+        let contenido = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        let temporal = format!("{}.tmp", ruta);
+        std::fs::write(&temporal, contenido).map_err(|err| err.to_string())?;
+        std::fs::rename(&temporal, ruta).map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,445 @@
+use crate::descuentos::Descuento;
+use crate::pagos::pedir_pagos;
+use crate::ventas::{total_venta, LineaVenta};
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use chrono::Local;
+
+// Carrito de la venta en curso en el punto de venta
+pub struct Carrito {
+    pub lineas: Vec<LineaVenta>,
+    pub descuento: Option<Descuento>,
+    pub cliente: Option<String>,
+}
+
+impl Carrito {
+    pub fn new() -> Carrito {
+        Carrito {
+            lineas: Vec::new(),
+            descuento: None,
+            cliente: None,
+        }
+    }
+
+    pub fn agregar(
+        &mut self,
+        gestor_productos: &GestorProductos,
+        nombre: &str,
+        cantidad: f64,
+    ) -> Result<(), String> {
+        if cantidad <= 0.0 {
+            return Err("Cantidad no válida".to_string());
+        }
+        let en_carrito = self
+            .lineas
+            .iter()
+            .find(|l| l.producto == nombre)
+            .map_or(0.0, |l| l.cantidad);
+        if gestor_productos.disponibilidad(nombre) < en_carrito + cantidad {
+            return Err("Cantidad insuficiente en el inventario".to_string());
+        }
+
+        match self.lineas.iter_mut().find(|l| l.producto == nombre) {
+            Some(linea) => linea.cantidad += cantidad,
+            None => self
+                .lineas
+                .push(gestor_productos.nueva_linea_venta(nombre, cantidad)?),
+        }
+        Ok(())
+    }
+
+    pub fn cambiar_cantidad(
+        &mut self,
+        gestor_productos: &GestorProductos,
+        indice: usize,
+        cantidad: f64,
+    ) -> Result<(), String> {
+        if cantidad <= 0.0 {
+            return Err("Cantidad no válida".to_string());
+        }
+        let linea = self
+            .lineas
+            .get_mut(indice)
+            .ok_or_else(|| "Línea no encontrada".to_string())?;
+        if gestor_productos.disponibilidad(&linea.producto) < cantidad {
+            return Err("Cantidad insuficiente en el inventario".to_string());
+        }
+        linea.cantidad = cantidad;
+        Ok(())
+    }
+
+    pub fn quitar(&mut self, indice: usize) -> Result<(), String> {
+        if indice >= self.lineas.len() {
+            return Err("Línea no encontrada".to_string());
+        }
+        self.lineas.remove(indice);
+        Ok(())
+    }
+
+    pub fn descontar_linea(
+        &mut self,
+        gestor_productos: &GestorProductos,
+        indice: usize,
+        descuento: Descuento,
+        es_administrador: bool,
+    ) -> Result<(), String> {
+        let linea = self
+            .lineas
+            .get_mut(indice)
+            .ok_or_else(|| "Línea no encontrada".to_string())?;
+        let base = linea.bruto() - linea.descuento_promocion();
+        gestor_productos.validar_descuento(base, &descuento, es_administrador)?;
+        linea.descuento = Some(descuento);
+        Ok(())
+    }
+
+    pub fn descontar(
+        &mut self,
+        gestor_productos: &GestorProductos,
+        descuento: Descuento,
+        es_administrador: bool,
+    ) -> Result<(), String> {
+        let suma = self.lineas.iter().map(LineaVenta::subtotal).sum();
+        gestor_productos.validar_descuento(suma, &descuento, es_administrador)?;
+        self.descuento = Some(descuento);
+        Ok(())
+    }
+
+    // Quita los descuentos que con los montos actuales superan el límite del rol y devuelve
+    // un aviso por cada uno; se llama después de cada cambio en las líneas o en los precios
+    pub fn revisar_descuentos(
+        &mut self,
+        gestor_productos: &GestorProductos,
+        es_administrador: bool,
+    ) -> Vec<String> {
+        let mut avisos = Vec::new();
+        for linea in self.lineas.iter_mut() {
+            let Some(descuento) = linea.descuento else {
+                continue;
+            };
+            let base = linea.bruto() - linea.descuento_promocion();
+            if let Err(err) = gestor_productos.validar_descuento(base, &descuento, es_administrador)
+            {
+                linea.descuento = None;
+                avisos.push(format!(
+                    "Se quitó el descuento de {}: {}",
+                    linea.producto, err
+                ));
+            }
+        }
+        if let Some(descuento) = self.descuento {
+            let suma = self.lineas.iter().map(LineaVenta::subtotal).sum();
+            if let Err(err) = gestor_productos.validar_descuento(suma, &descuento, es_administrador)
+            {
+                self.descuento = None;
+                avisos.push(format!("Se quitó el descuento de la venta: {}", err));
+            }
+        }
+        avisos
+    }
+
+    pub fn total(&self) -> f64 {
+        total_venta(&self.lineas, self.descuento)
+    }
+
+    fn mostrar(&self) {
+        if let Some(cliente) = &self.cliente {
+            println!("Cliente: {}", cliente);
+        }
+        if self.lineas.is_empty() {
+            println!("(carrito vacío)");
+        }
+        for (i, linea) in self.lineas.iter().enumerate() {
+            println!(
+                "{}. {} x {} @ ${:.2} = ${:.2}",
+                i + 1,
+                linea.cantidad,
+                linea.producto,
+                linea.precio_unitario,
+                linea.subtotal()
+            );
+            if let Some((nombre, monto)) = &linea.promocion {
+                println!("   {}: -${:.2}", nombre, monto);
+            }
+            if let Some(descuento) = linea.descuento {
+                println!(
+                    "   Descuento {}: -${:.2}",
+                    descuento.descripcion(),
+                    linea.descuento_manual()
+                );
+            }
+        }
+        if let Some(descuento) = self.descuento {
+            println!("Descuento de la venta {}", descuento.descripcion());
+        }
+        println!("TOTAL: ${:.2}", self.total());
+    }
+}
+
+// Busca productos cuyo nombre contiene el texto y permite elegir uno
+fn buscar_y_elegir(gestor_productos: &GestorProductos, texto: &str) -> Option<String> {
+    let texto = texto.to_lowercase();
+    let mut encontrados: Vec<&String> = gestor_productos
+        .productos
+        .keys()
+        .filter(|nombre| nombre.to_lowercase().contains(&texto))
+        .collect();
+    encontrados.sort();
+    if encontrados.is_empty() {
+        println!("No se encontraron productos");
+        return None;
+    }
+    for (i, nombre) in encontrados.iter().enumerate() {
+        println!(
+            "{}. {} (disponible: {})",
+            i + 1,
+            nombre,
+            gestor_productos.disponibilidad(nombre)
+        );
+    }
+    println!("Seleccione un número (deje vacío para cancelar):");
+    let opcion: usize = leer_entrada().parse().ok()?;
+    encontrados
+        .get(opcion.checked_sub(1)?)
+        .map(|nombre| nombre.to_string())
+}
+
+pub fn menu_pos(
+    gestor_productos: &mut GestorProductos,
+    usuario_actual: &str,
+    es_administrador: bool,
+) {
+    let mut carrito = Carrito::new();
+    let mut mensaje = String::new();
+
+    if gestor_productos.sesion_abierta(usuario_actual).is_none() {
+        limpiar_consola();
+        println!("No tiene una caja abierta, las ventas no quedarán asociadas a una sesión.");
+        println!(
+            "Ingrese el fondo inicial para abrir la caja (deje vacío para continuar sin caja):"
+        );
+        let fondo = leer_entrada();
+        if !fondo.is_empty() {
+            let resultado = match fondo.parse::<f64>() {
+                Ok(fondo) => gestor_productos.abrir_caja(usuario_actual, fondo),
+                Err(_) => Err("Monto no válido".to_string()),
+            };
+            match resultado {
+                Ok(id) => mensaje = format!("Sesión de caja #{} abierta", id),
+                Err(err) => mensaje = format!("Error al abrir la caja: {}", err),
+            }
+        }
+    }
+
+    loop {
+        gestor_productos.aplicar_cambios_programados(Local::now().date_naive());
+        gestor_productos.resolver_precios(&mut carrito.lineas, carrito.cliente.as_deref());
+        gestor_productos.aplicar_promociones(&mut carrito.lineas);
+        for aviso in carrito.revisar_descuentos(gestor_productos, es_administrador) {
+            if !mensaje.is_empty() {
+                mensaje.push('\n');
+            }
+            mensaje.push_str(&aviso);
+        }
+        limpiar_consola();
+        println!("Punto de Venta");
+        println!("--------------");
+        carrito.mostrar();
+        println!();
+        println!("Escanee un código o escriba nombre/SKU para agregar (3*código agrega 3)");
+        println!("?texto buscar | m N cantidad modificar | e N eliminar | p pagar | x salir");
+        println!("d N 10% descuento en línea | d 5 descuento en la venta | c nombre cliente");
+        if !mensaje.is_empty() {
+            println!("{}", mensaje);
+            mensaje.clear();
+        }
+
+        let entrada = leer_entrada();
+        let mut partes = entrada.split_whitespace();
+        match (partes.next(), partes.next(), partes.next()) {
+            (None, _, _) => {}
+            (Some("x"), None, _) => {
+                if !carrito.lineas.is_empty() {
+                    println!("Se descartará el carrito, ¿desea salir? (s/n):");
+                    if leer_entrada().to_lowercase() != "s" {
+                        continue;
+                    }
+                }
+                break;
+            }
+            (Some("e"), Some(numero), None) => {
+                let resultado = match numero.parse::<usize>() {
+                    Ok(numero) if numero > 0 => carrito.quitar(numero - 1),
+                    _ => Err("Número de línea no válido".to_string()),
+                };
+                if let Err(err) = resultado {
+                    mensaje = err;
+                }
+            }
+            (Some("m"), Some(numero), Some(cantidad)) => {
+                let resultado = match (numero.parse::<usize>(), cantidad.parse::<f64>()) {
+                    (Ok(numero), Ok(cantidad)) if numero > 0 => {
+                        carrito.cambiar_cantidad(gestor_productos, numero - 1, cantidad)
+                    }
+                    _ => Err("Número de línea o cantidad no válidos".to_string()),
+                };
+                if let Err(err) = resultado {
+                    mensaje = err;
+                }
+            }
+            (Some("c"), _, _) => {
+                let nombre = entrada[1..].trim();
+                if nombre.is_empty() {
+                    carrito.cliente = None;
+                } else {
+                    match gestor_productos.buscar_cliente(nombre) {
+                        Some(cliente) => carrito.cliente = Some(cliente),
+                        None => mensaje = format!("Cliente no encontrado: {}", nombre),
+                    }
+                }
+            }
+            (Some("d"), Some(valor), None) => {
+                let resultado = match Descuento::interpretar(valor) {
+                    Some(descuento) => {
+                        carrito.descontar(gestor_productos, descuento, es_administrador)
+                    }
+                    None => Err("Descuento no válido".to_string()),
+                };
+                if let Err(err) = resultado {
+                    mensaje = err;
+                }
+            }
+            (Some("d"), Some(numero), Some(valor)) => {
+                let resultado = match (numero.parse::<usize>(), Descuento::interpretar(valor)) {
+                    (Ok(numero), Some(descuento)) if numero > 0 => carrito.descontar_linea(
+                        gestor_productos,
+                        numero - 1,
+                        descuento,
+                        es_administrador,
+                    ),
+                    _ => Err("Número de línea o descuento no válidos".to_string()),
+                };
+                if let Err(err) = resultado {
+                    mensaje = err;
+                }
+            }
+            (Some("p"), None, _) => {
+                if carrito.lineas.is_empty() {
+                    mensaje = "El carrito está vacío".to_string();
+                    continue;
+                }
+                println!("Total a pagar: ${:.2}", carrito.total());
+                let Some((pagos, cambio)) = pedir_pagos(carrito.total()) else {
+                    mensaje = "Pago cancelado".to_string();
+                    continue;
+                };
+
+                match gestor_productos.registrar_venta(
+                    carrito.lineas.clone(),
+                    carrito.cliente.as_deref(),
+                    carrito.descuento,
+                    pagos,
+                    usuario_actual,
+                    es_administrador,
+                ) {
+                    Ok(id) => {
+                        limpiar_consola();
+                        if let Some(venta) = gestor_productos.buscar_venta(id) {
+                            venta.mostrar_ticket();
+                        }
+                        println!("Cambio: ${:.2}", cambio);
+                        carrito = Carrito::new();
+                        pausar();
+                    }
+                    Err(err) => mensaje = format!("Error al registrar la venta: {}", err),
+                }
+            }
+            _ => {
+                let (cantidad, texto) = match entrada.split_once('*') {
+                    Some((cantidad, texto)) => match cantidad.trim().parse::<f64>() {
+                        Ok(cantidad) => (cantidad, texto.trim()),
+                        Err(_) => (1.0, entrada.as_str()),
+                    },
+                    None => (1.0, entrada.as_str()),
+                };
+                let nombre = match texto.strip_prefix('?') {
+                    Some(busqueda) => buscar_y_elegir(gestor_productos, busqueda.trim()),
+                    None => gestor_productos.resolver_producto(texto),
+                };
+                match nombre {
+                    Some(nombre) => {
+                        if let Err(err) = carrito.agregar(gestor_productos, &nombre, cantidad) {
+                            mensaje = format!("{}: {}", nombre, err);
+                        }
+                    }
+                    None => mensaje = format!("Producto no encontrado: {}", texto),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Producto;
+
+    fn gestor_con_productos() -> GestorProductos {
+        let mut gestor = GestorProductos::new();
+        for (nombre, precio) in [("Licuadora", 94.0), ("Vaso", 6.0)] {
+            let producto = Producto::new(nombre.to_string(), String::new(), precio, 10.0, 0.0);
+            gestor.agregar_producto(nombre.to_string(), producto);
+        }
+        gestor.configuracion.descuento_maximo_usuario = 10.0;
+        gestor
+    }
+
+    #[test]
+    fn descuento_fijo_se_quita_al_reducir_el_carrito() {
+        let gestor = gestor_con_productos();
+        let mut carrito = Carrito::new();
+        carrito.agregar(&gestor, "Licuadora", 1.0).unwrap();
+        carrito.agregar(&gestor, "Vaso", 1.0).unwrap();
+        // $5 sobre $100 es 5%, dentro del límite de 10%
+        carrito
+            .descontar(&gestor, Descuento::Monto(5.0), false)
+            .unwrap();
+        assert!(carrito.revisar_descuentos(&gestor, false).is_empty());
+
+        // Con solo el vaso los $5 serían 83% de $6
+        carrito.quitar(0).unwrap();
+        let avisos = carrito.revisar_descuentos(&gestor, false);
+        assert_eq!(avisos.len(), 1);
+        assert!(carrito.descuento.is_none());
+        assert_eq!(carrito.total(), 6.0);
+    }
+
+    #[test]
+    fn descuento_de_linea_se_revisa_al_cambiar_la_cantidad() {
+        let gestor = gestor_con_productos();
+        let mut carrito = Carrito::new();
+        carrito.agregar(&gestor, "Vaso", 5.0).unwrap();
+        carrito
+            .descontar_linea(&gestor, 0, Descuento::Monto(3.0), false)
+            .unwrap();
+        carrito.cambiar_cantidad(&gestor, 0, 1.0).unwrap();
+        carrito.revisar_descuentos(&gestor, false);
+        assert!(carrito.lineas[0].descuento.is_none());
+    }
+
+    #[test]
+    fn registrar_venta_rechaza_descuento_sobre_el_limite() {
+        let mut gestor = gestor_con_productos();
+        let lineas = vec![gestor.nueva_linea_venta("Vaso", 1.0).unwrap()];
+        let resultado = gestor.registrar_venta(
+            lineas,
+            None,
+            Some(Descuento::Monto(5.0)),
+            Vec::new(),
+            "cajero@tienda.com",
+            false,
+        );
+        assert!(resultado.is_err_and(|err| err.contains("límite")));
+        assert_eq!(gestor.productos["Vaso"].cantidad_disponible, 10.0);
+        assert!(gestor.ventas.is_empty());
+    }
+}
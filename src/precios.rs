@@ -0,0 +1,317 @@
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, pedir_producto, GestorProductos};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// Registro de un cambio en el precio base de un producto
+#[derive(Serialize, Deserialize)]
+pub struct CambioPrecio {
+    pub fecha: DateTime<Local>,
+    pub producto: String,
+    pub anterior: f64,
+    pub nuevo: f64,
+    pub usuario: String,
+    // Número del cambio programado que lo originó
+    pub programado: Option<u32>,
+}
+
+// Cambio de precio que se aplica automáticamente al llegar la fecha
+#[derive(Serialize, Deserialize)]
+pub struct CambioProgramado {
+    pub id: u32,
+    pub producto: String,
+    pub precio: f64,
+    pub fecha: NaiveDate,
+    pub usuario: String,
+    pub aplicado: bool,
+    pub cancelado: bool,
+}
+
+impl CambioProgramado {
+    pub fn pendiente(&self) -> bool {
+        !self.aplicado && !self.cancelado
+    }
+}
+
+// Implementación de métodos de historial de precios para GestorProductos
+impl GestorProductos {
+    pub fn cambiar_precio(
+        &mut self,
+        nombre: &str,
+        precio: f64,
+        usuario: &str,
+    ) -> Result<(), String> {
+        self.registrar_cambio_precio(nombre, precio, usuario, None)
+    }
+
+    fn registrar_cambio_precio(
+        &mut self,
+        nombre: &str,
+        precio: f64,
+        usuario: &str,
+        programado: Option<u32>,
+    ) -> Result<(), String> {
+        if precio < 0.0 {
+            return Err("Precio no válido".to_string());
+        }
+        let producto = self
+            .productos
+            .get_mut(nombre)
+            .ok_or_else(|| "Producto no encontrado".to_string())?;
+        let anterior = producto.precio;
+        producto.precio = precio;
+        self.historial_precios.push(CambioPrecio {
+            fecha: Local::now(),
+            producto: nombre.to_string(),
+            anterior,
+            nuevo: precio,
+            usuario: usuario.to_string(),
+            programado,
+        });
+        Ok(())
+    }
+
+    pub fn programar_cambio_precio(
+        &mut self,
+        nombre: &str,
+        precio: f64,
+        fecha: NaiveDate,
+        usuario: &str,
+    ) -> Result<u32, String> {
+        if !self.productos.contains_key(nombre) {
+            return Err("Producto no encontrado".to_string());
+        }
+        if precio < 0.0 {
+            return Err("Precio no válido".to_string());
+        }
+        if fecha <= Local::now().date_naive() {
+            return Err("La fecha debe ser posterior a hoy, use Cambiar Precio".to_string());
+        }
+        let id = self.cambios_programados.len() as u32 + 1;
+        self.cambios_programados.push(CambioProgramado {
+            id,
+            producto: nombre.to_string(),
+            precio,
+            fecha,
+            usuario: usuario.to_string(),
+            aplicado: false,
+            cancelado: false,
+        });
+        Ok(id)
+    }
+
+    pub fn cancelar_cambio_programado(&mut self, id: u32) -> Result<(), String> {
+        let cambio = self
+            .cambios_programados
+            .iter_mut()
+            .find(|c| c.id == id && c.pendiente())
+            .ok_or_else(|| "Cambio programado pendiente no encontrado".to_string())?;
+        cambio.cancelado = true;
+        Ok(())
+    }
+
+    // Aplica en orden de fecha los cambios programados que ya llegaron, devuelve cuántos aplicó
+    pub fn aplicar_cambios_programados(&mut self, hoy: NaiveDate) -> usize {
+        let mut pendientes: Vec<(NaiveDate, u32)> = self
+            .cambios_programados
+            .iter()
+            .filter(|c| c.pendiente() && c.fecha <= hoy)
+            .map(|c| (c.fecha, c.id))
+            .collect();
+        pendientes.sort();
+
+        let mut aplicados = 0;
+        for (_, id) in pendientes {
+            let Some(cambio) = self.cambios_programados.iter_mut().find(|c| c.id == id) else {
+                continue;
+            };
+            cambio.aplicado = true;
+            let (producto, precio, usuario) = (
+                cambio.producto.clone(),
+                cambio.precio,
+                cambio.usuario.clone(),
+            );
+            if self
+                .registrar_cambio_precio(&producto, precio, &usuario, Some(id))
+                .is_ok()
+            {
+                aplicados += 1;
+            }
+        }
+        aplicados
+    }
+
+    pub fn cambios_precio_periodo(&self, desde: NaiveDate, hasta: NaiveDate) -> Vec<&CambioPrecio> {
+        self.historial_precios
+            .iter()
+            .filter(|c| (desde..=hasta).contains(&c.fecha.date_naive()))
+            .collect()
+    }
+}
+
+impl CambioPrecio {
+    pub fn mostrar(&self) {
+        let variacion = if self.anterior > 0.0 {
+            format!(
+                " ({:+.1}%)",
+                (self.nuevo - self.anterior) / self.anterior * 100.0
+            )
+        } else {
+            String::new()
+        };
+        println!(
+            "{} - {} - ${:.2} -> ${:.2}{} - {}{}",
+            self.fecha.format("%Y-%m-%d %H:%M"),
+            self.producto,
+            self.anterior,
+            self.nuevo,
+            variacion,
+            self.usuario,
+            match self.programado {
+                Some(id) => format!(" (programado #{})", id),
+                None => String::new(),
+            }
+        );
+    }
+}
+
+pub fn menu_precios(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Precios");
+        println!("-------");
+        println!("1. Cambiar Precio");
+        println!("2. Programar Cambio de Precio");
+        println!("3. Ver Cambios Programados");
+        println!("4. Cancelar Cambio Programado");
+        println!("5. Reporte de Cambios por Período");
+        println!("6. Historial de Precios de un Producto");
+        println!("7. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                let Some(producto) = pedir_producto(
+                    gestor_productos,
+                    "Ingrese el nombre, SKU o código de barras del producto:",
+                ) else {
+                    pausar();
+                    continue;
+                };
+                if let Some(actual) = gestor_productos.buscar_producto(&producto) {
+                    println!("Precio actual: ${:.2}", actual.precio);
+                }
+                println!("Ingrese el nuevo precio:");
+                let resultado = match leer_entrada().parse::<f64>() {
+                    Ok(precio) => {
+                        gestor_productos.cambiar_precio(&producto, precio, usuario_actual)
+                    }
+                    Err(_) => Err("Precio no válido".to_string()),
+                };
+                match resultado {
+                    Ok(()) => println!("Precio actualizado"),
+                    Err(err) => println!("Error al cambiar el precio: {}", err),
+                }
+            }
+            "2" => {
+                let Some(producto) = pedir_producto(
+                    gestor_productos,
+                    "Ingrese el nombre, SKU o código de barras del producto:",
+                ) else {
+                    pausar();
+                    continue;
+                };
+                println!("Ingrese el nuevo precio:");
+                let precio = leer_entrada().parse::<f64>();
+                let Some(fecha) = pedir_fecha("Ingrese la fecha en que aplica (AAAA-MM-DD):")
+                else {
+                    pausar();
+                    continue;
+                };
+                let resultado = match precio {
+                    Ok(precio) => gestor_productos.programar_cambio_precio(
+                        &producto,
+                        precio,
+                        fecha,
+                        usuario_actual,
+                    ),
+                    Err(_) => Err("Precio no válido".to_string()),
+                };
+                match resultado {
+                    Ok(id) => println!("Cambio programado #{}", id),
+                    Err(err) => println!("Error al programar el cambio: {}", err),
+                }
+            }
+            "3" => {
+                let mut pendientes: Vec<_> = gestor_productos
+                    .cambios_programados
+                    .iter()
+                    .filter(|c| c.pendiente())
+                    .collect();
+                if pendientes.is_empty() {
+                    println!("No hay cambios programados pendientes");
+                }
+                pendientes.sort_by_key(|c| c.fecha);
+                for cambio in pendientes {
+                    println!(
+                        "#{} - {} - {} a ${:.2} - {}",
+                        cambio.id, cambio.fecha, cambio.producto, cambio.precio, cambio.usuario
+                    );
+                }
+            }
+            "4" => {
+                println!("Ingrese el número del cambio programado:");
+                let resultado = match leer_entrada().trim_start_matches('#').parse::<u32>() {
+                    Ok(id) => gestor_productos.cancelar_cambio_programado(id),
+                    Err(_) => Err("Número no válido".to_string()),
+                };
+                match resultado {
+                    Ok(()) => println!("Cambio programado cancelado"),
+                    Err(err) => println!("Error al cancelar el cambio: {}", err),
+                }
+            }
+            "5" => {
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                let cambios = gestor_productos.cambios_precio_periodo(desde, hasta);
+                if cambios.is_empty() {
+                    println!("No hubo cambios de precio en el período");
+                }
+                for cambio in &cambios {
+                    cambio.mostrar();
+                }
+                println!("Total de cambios: {}", cambios.len());
+            }
+            "6" => {
+                let Some(producto) = pedir_producto(
+                    gestor_productos,
+                    "Ingrese el nombre, SKU o código de barras del producto:",
+                ) else {
+                    pausar();
+                    continue;
+                };
+                let cambios: Vec<_> = gestor_productos
+                    .historial_precios
+                    .iter()
+                    .filter(|c| c.producto == producto)
+                    .collect();
+                if cambios.is_empty() {
+                    println!("El precio de {} no ha cambiado", producto);
+                }
+                for cambio in cambios {
+                    cambio.mostrar();
+                }
+            }
+            "7" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
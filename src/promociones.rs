@@ -0,0 +1,343 @@
+use crate::categorias::pedir_categoria;
+use crate::ventas::LineaVenta;
+use crate::{
+    leer_entrada, limpiar_consola, pausar, pedir_fecha, pedir_numero, pedir_producto,
+    GestorProductos,
+};
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub enum TipoPromocion {
+    // Lleva X unidades y paga Y, por ejemplo 2x1 o 3x2
+    LlevaPaga {
+        producto: String,
+        lleva: f64,
+        paga: f64,
+    },
+    // Por cada X unidades de un producto se regalan Y unidades de otro
+    CompraRecibe {
+        producto: String,
+        compra: f64,
+        regalo: String,
+        cantidad_regalo: f64,
+    },
+    // Porcentaje de descuento en una categoría y sus subcategorías
+    Categoria {
+        categoria: u32,
+        porcentaje: f64,
+    },
+}
+
+// Promoción válida entre dos fechas, se aplica automáticamente en las ventas
+#[derive(Serialize, Deserialize)]
+pub struct Promocion {
+    pub id: u32,
+    pub nombre: String,
+    pub tipo: TipoPromocion,
+    pub desde: NaiveDate,
+    pub hasta: NaiveDate,
+    pub activa: bool,
+}
+
+impl Promocion {
+    pub fn vigente(&self, fecha: NaiveDate) -> bool {
+        self.activa && (self.desde..=self.hasta).contains(&fecha)
+    }
+
+    // Descuento que la promoción da a la línea indicada dentro de la venta
+    fn descuento_linea(
+        &self,
+        gestor_productos: &GestorProductos,
+        lineas: &[LineaVenta],
+        linea: &LineaVenta,
+    ) -> f64 {
+        match &self.tipo {
+            TipoPromocion::LlevaPaga {
+                producto,
+                lleva,
+                paga,
+            } if *producto == linea.producto => {
+                let grupos = (linea.cantidad / lleva).floor();
+                grupos * (lleva - paga) * linea.precio_unitario
+            }
+            TipoPromocion::CompraRecibe {
+                producto,
+                compra,
+                regalo,
+                cantidad_regalo,
+            } if *regalo == linea.producto => {
+                let compradas: f64 = lineas
+                    .iter()
+                    .filter(|l| l.producto == *producto)
+                    .map(|l| l.cantidad)
+                    .sum();
+                let regalos = (compradas / compra).floor() * cantidad_regalo;
+                regalos.min(linea.cantidad) * linea.precio_unitario
+            }
+            TipoPromocion::Categoria {
+                categoria,
+                porcentaje,
+            } => {
+                let en_categoria = gestor_productos
+                    .productos
+                    .get(&linea.producto)
+                    .and_then(|p| p.categoria)
+                    .is_some_and(|c| gestor_productos.subcategorias(*categoria).contains(&c));
+                if en_categoria {
+                    linea.bruto() * porcentaje / 100.0
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn descripcion(&self, gestor_productos: &GestorProductos) -> String {
+        match &self.tipo {
+            TipoPromocion::LlevaPaga {
+                producto,
+                lleva,
+                paga,
+            } => format!("Lleva {} paga {} en {}", lleva, paga, producto),
+            TipoPromocion::CompraRecibe {
+                producto,
+                compra,
+                regalo,
+                cantidad_regalo,
+            } => format!(
+                "Por cada {} de {} recibe {} de {}",
+                compra, producto, cantidad_regalo, regalo
+            ),
+            TipoPromocion::Categoria {
+                categoria,
+                porcentaje,
+            } => format!(
+                "{}% en {}",
+                porcentaje,
+                gestor_productos.ruta_categoria(*categoria)
+            ),
+        }
+    }
+}
+
+// Implementación de métodos de promociones para GestorProductos
+impl GestorProductos {
+    pub fn agregar_promocion(
+        &mut self,
+        nombre: &str,
+        tipo: TipoPromocion,
+        desde: NaiveDate,
+        hasta: NaiveDate,
+    ) -> Result<u32, String> {
+        if nombre.is_empty() {
+            return Err("La promoción debe tener un nombre".to_string());
+        }
+        if hasta < desde {
+            return Err("La fecha final es anterior a la inicial".to_string());
+        }
+        match &tipo {
+            TipoPromocion::LlevaPaga {
+                producto,
+                lleva,
+                paga,
+            } => {
+                if !self.productos.contains_key(producto) {
+                    return Err("Producto no encontrado".to_string());
+                }
+                if *paga <= 0.0 || lleva <= paga {
+                    return Err("Debe llevar más unidades de las que paga".to_string());
+                }
+            }
+            TipoPromocion::CompraRecibe {
+                producto,
+                compra,
+                regalo,
+                cantidad_regalo,
+            } => {
+                if !self.productos.contains_key(producto) || !self.productos.contains_key(regalo) {
+                    return Err("Producto no encontrado".to_string());
+                }
+                if producto == regalo {
+                    return Err(
+                        "Para el mismo producto use una promoción lleva X paga Y".to_string()
+                    );
+                }
+                if *compra <= 0.0 || *cantidad_regalo <= 0.0 {
+                    return Err("Cantidades no válidas".to_string());
+                }
+            }
+            TipoPromocion::Categoria {
+                categoria,
+                porcentaje,
+            } => {
+                if !self.categorias.contains_key(categoria) {
+                    return Err("Categoría no encontrada".to_string());
+                }
+                if *porcentaje <= 0.0 || *porcentaje > 100.0 {
+                    return Err("Porcentaje no válido".to_string());
+                }
+            }
+        }
+
+        let id = self.promociones.len() as u32 + 1;
+        self.promociones.push(Promocion {
+            id,
+            nombre: nombre.to_string(),
+            tipo,
+            desde,
+            hasta,
+            activa: true,
+        });
+        Ok(id)
+    }
+
+    pub fn desactivar_promocion(&mut self, id: u32) -> Result<(), String> {
+        match self.promociones.iter_mut().find(|p| p.id == id) {
+            Some(promocion) => {
+                promocion.activa = false;
+                Ok(())
+            }
+            None => Err("Promoción no encontrada".to_string()),
+        }
+    }
+
+    // Las promociones no se acumulan, cada línea recibe la de mayor descuento vigente hoy
+    pub fn aplicar_promociones(&self, lineas: &mut [LineaVenta]) {
+        let hoy = Local::now().date_naive();
+        let descuentos: Vec<Option<(String, f64)>> = lineas
+            .iter()
+            .map(|linea| {
+                self.promociones
+                    .iter()
+                    .filter(|p| p.vigente(hoy))
+                    .map(|p| {
+                        let monto = p.descuento_linea(self, lineas, linea).min(linea.bruto());
+                        (p.nombre.clone(), monto)
+                    })
+                    .filter(|(_, monto)| *monto > 0.0)
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+            })
+            .collect();
+        for (linea, promocion) in lineas.iter_mut().zip(descuentos) {
+            linea.promocion = promocion;
+        }
+    }
+}
+
+fn pedir_lleva_paga(gestor_productos: &GestorProductos) -> Option<TipoPromocion> {
+    let producto = pedir_producto(gestor_productos, "Ingrese el producto:")?;
+    let lleva = pedir_numero("¿Cuántas unidades lleva?")?;
+    let paga = pedir_numero("¿Cuántas unidades paga?")?;
+    Some(TipoPromocion::LlevaPaga {
+        producto,
+        lleva,
+        paga,
+    })
+}
+
+fn pedir_compra_recibe(gestor_productos: &GestorProductos) -> Option<TipoPromocion> {
+    let producto = pedir_producto(gestor_productos, "Ingrese el producto a comprar:")?;
+    let compra = pedir_numero("¿Cuántas unidades debe comprar?")?;
+    let regalo = pedir_producto(gestor_productos, "Ingrese el producto de regalo:")?;
+    let cantidad_regalo = pedir_numero("¿Cuántas unidades se regalan?")?;
+    Some(TipoPromocion::CompraRecibe {
+        producto,
+        compra,
+        regalo,
+        cantidad_regalo,
+    })
+}
+
+fn pedir_descuento_categoria(gestor_productos: &GestorProductos) -> Option<TipoPromocion> {
+    let categoria = pedir_categoria(gestor_productos)?;
+    let porcentaje = pedir_numero("Ingrese el porcentaje de descuento:")?;
+    Some(TipoPromocion::Categoria {
+        categoria,
+        porcentaje,
+    })
+}
+
+pub fn menu_promociones(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Promociones");
+        println!("-----------");
+        println!("1. Ver Promociones");
+        println!("2. Crear Promoción Lleva X Paga Y (2x1, 3x2)");
+        println!("3. Crear Promoción Compra X Recibe Y");
+        println!("4. Crear Descuento por Categoría");
+        println!("5. Desactivar Promoción");
+        println!("6. Volver al Menú Principal");
+
+        let opcion = leer_entrada();
+        match opcion.as_str() {
+            "1" => {
+                if gestor_productos.promociones.is_empty() {
+                    println!("No hay promociones registradas");
+                }
+                let hoy = Local::now().date_naive();
+                for promocion in &gestor_productos.promociones {
+                    println!(
+                        "#{} - {} - {} - {} a {}{}",
+                        promocion.id,
+                        promocion.nombre,
+                        promocion.descripcion(gestor_productos),
+                        promocion.desde,
+                        promocion.hasta,
+                        if promocion.vigente(hoy) {
+                            " (vigente)"
+                        } else if !promocion.activa {
+                            " (desactivada)"
+                        } else {
+                            ""
+                        }
+                    );
+                }
+            }
+            "2" | "3" | "4" => {
+                println!("Ingrese el nombre de la promoción:");
+                let nombre = leer_entrada();
+                let tipo = match opcion.as_str() {
+                    "2" => pedir_lleva_paga(gestor_productos),
+                    "3" => pedir_compra_recibe(gestor_productos),
+                    _ => pedir_descuento_categoria(gestor_productos),
+                };
+                let Some(tipo) = tipo else {
+                    pausar();
+                    continue;
+                };
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha de inicio (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha de fin (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                match gestor_productos.agregar_promocion(&nombre, tipo, desde, hasta) {
+                    Ok(id) => println!("Promoción #{} creada", id),
+                    Err(err) => println!("Error al crear la promoción: {}", err),
+                }
+            }
+            "5" => {
+                println!("Ingrese el número de la promoción:");
+                let resultado = match leer_entrada().trim_start_matches('#').parse::<u32>() {
+                    Ok(id) => gestor_productos.desactivar_promocion(id),
+                    Err(_) => Err("Número no válido".to_string()),
+                };
+                match resultado {
+                    Ok(()) => println!("Promoción desactivada"),
+                    Err(err) => println!("Error al desactivar la promoción: {}", err),
+                }
+            }
+            "6" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
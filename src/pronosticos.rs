@@ -0,0 +1,528 @@
+use crate::{leer_entrada, limpiar_consola, pausar, GestorProductos};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Periodo {
+    Dia,
+    Semana,
+    Mes,
+}
+
+impl Periodo {
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Periodo::Dia => "día",
+            Periodo::Semana => "semana",
+            Periodo::Mes => "mes",
+        }
+    }
+
+    pub fn dias(&self) -> f64 {
+        match self {
+            Periodo::Dia => 1.0,
+            Periodo::Semana => 7.0,
+            Periodo::Mes => 30.0,
+        }
+    }
+
+    // Primer día del período que contiene la fecha, las semanas empiezan el lunes
+    pub fn inicio(&self, fecha: NaiveDate) -> NaiveDate {
+        match self {
+            Periodo::Dia => fecha,
+            Periodo::Semana => {
+                fecha - Duration::days(fecha.weekday().num_days_from_monday() as i64)
+            }
+            Periodo::Mes => fecha.with_day(1).unwrap_or(fecha),
+        }
+    }
+
+    pub fn siguiente(&self, inicio: NaiveDate) -> NaiveDate {
+        match self {
+            Periodo::Dia => inicio + Duration::days(1),
+            Periodo::Semana => inicio + Duration::days(7),
+            Periodo::Mes => {
+                let (anio, mes) = if inicio.month() == 12 {
+                    (inicio.year() + 1, 1)
+                } else {
+                    (inicio.year(), inicio.month() + 1)
+                };
+                NaiveDate::from_ymd_opt(anio, mes, 1).unwrap_or(inicio)
+            }
+        }
+    }
+
+    pub fn anterior(&self, inicio: NaiveDate) -> NaiveDate {
+        match self {
+            Periodo::Dia => inicio - Duration::days(1),
+            Periodo::Semana => inicio - Duration::days(7),
+            Periodo::Mes => {
+                let (anio, mes) = if inicio.month() == 1 {
+                    (inicio.year() - 1, 12)
+                } else {
+                    (inicio.year(), inicio.month() - 1)
+                };
+                NaiveDate::from_ymd_opt(anio, mes, 1).unwrap_or(inicio)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum MetodoPronostico {
+    // Promedio de los últimos N períodos
+    PromedioMovil(usize),
+    // Suavizado exponencial simple con factor alfa entre 0 y 1
+    SuavizadoExponencial(f64),
+    // Tendencia lineal por índices estacionales, con la longitud de la temporada en períodos
+    Estacional(usize),
+}
+
+impl MetodoPronostico {
+    pub fn descripcion(&self) -> String {
+        match self {
+            MetodoPronostico::PromedioMovil(ventana) => {
+                format!("promedio móvil de {} períodos", ventana)
+            }
+            MetodoPronostico::SuavizadoExponencial(alfa) => {
+                format!("suavizado exponencial (alfa {})", alfa)
+            }
+            MetodoPronostico::Estacional(temporada) => {
+                format!(
+                    "descomposición estacional (temporada de {} períodos)",
+                    temporada
+                )
+            }
+        }
+    }
+
+    // Demanda esperada para el período siguiente a la serie
+    pub fn pronosticar(&self, serie: &[f64]) -> Option<f64> {
+        match *self {
+            MetodoPronostico::PromedioMovil(ventana) => promedio_movil(serie, ventana),
+            MetodoPronostico::SuavizadoExponencial(alfa) => suavizado_exponencial(serie, alfa),
+            MetodoPronostico::Estacional(temporada) => descomposicion_estacional(serie, temporada),
+        }
+    }
+}
+
+pub struct Pronostico {
+    pub producto: String,
+    pub demanda: f64,
+    pub desviacion: f64,
+    pub stock_seguridad: f64,
+    pub minima_actual: f64,
+    pub minima_propuesta: f64,
+}
+
+pub fn promedio_movil(serie: &[f64], ventana: usize) -> Option<f64> {
+    if ventana == 0 || serie.len() < ventana {
+        return None;
+    }
+    let ultimos = &serie[serie.len() - ventana..];
+    Some(ultimos.iter().sum::<f64>() / ventana as f64)
+}
+
+pub fn suavizado_exponencial(serie: &[f64], alfa: f64) -> Option<f64> {
+    if !(0.0..=1.0).contains(&alfa) {
+        return None;
+    }
+    let (primero, resto) = serie.split_first()?;
+    Some(
+        resto
+            .iter()
+            .fold(*primero, |nivel, valor| alfa * valor + (1.0 - alfa) * nivel),
+    )
+}
+
+// Descomposición multiplicativa: índices por posición en la temporada y recta de tendencia
+// sobre la serie desestacionalizada, necesita al menos dos temporadas completas
+pub fn descomposicion_estacional(serie: &[f64], temporada: usize) -> Option<f64> {
+    if temporada < 2 || serie.len() < temporada * 2 {
+        return None;
+    }
+    let media = serie.iter().sum::<f64>() / serie.len() as f64;
+    if media <= 0.0 {
+        return Some(0.0);
+    }
+    let indices: Vec<f64> = (0..temporada)
+        .map(|posicion| {
+            let valores: Vec<f64> = serie
+                .iter()
+                .skip(posicion)
+                .step_by(temporada)
+                .copied()
+                .collect();
+            valores.iter().sum::<f64>() / valores.len() as f64 / media
+        })
+        .collect();
+
+    let desestacionalizada: Vec<f64> = serie
+        .iter()
+        .enumerate()
+        .map(|(i, valor)| match indices[i % temporada] {
+            indice if indice > 0.0 => valor / indice,
+            _ => 0.0,
+        })
+        .collect();
+    let n = desestacionalizada.len() as f64;
+    let media_x = (n - 1.0) / 2.0;
+    let media_y = desestacionalizada.iter().sum::<f64>() / n;
+    let (covarianza, varianza) =
+        desestacionalizada
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(covarianza, varianza), (x, y)| {
+                let dx = x as f64 - media_x;
+                (covarianza + dx * (y - media_y), varianza + dx * dx)
+            });
+    let pendiente = covarianza / varianza;
+    let tendencia = media_y + pendiente * (n - media_x);
+    Some((tendencia * indices[serie.len() % temporada]).max(0.0))
+}
+
+pub fn desviacion_estandar(serie: &[f64]) -> f64 {
+    if serie.len() < 2 {
+        return 0.0;
+    }
+    let media = serie.iter().sum::<f64>() / serie.len() as f64;
+    let varianza =
+        serie.iter().map(|v| (v - media).powi(2)).sum::<f64>() / (serie.len() - 1) as f64;
+    varianza.sqrt()
+}
+
+// Valor z de la normal estándar para un nivel de servicio (aproximación de Abramowitz y Stegun)
+pub fn factor_servicio(nivel: f64) -> f64 {
+    if nivel <= 0.5 {
+        return 0.0;
+    }
+    let p = nivel.min(0.9999);
+    let t = (-2.0 * (1.0 - p).ln()).sqrt();
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t)
+}
+
+// Implementación de métodos de pronóstico para GestorProductos
+impl GestorProductos {
+    // Unidades vendidas menos devueltas en [desde, hasta), los kits cuentan como sus componentes
+    pub fn demanda_en(&self, nombre: &str, desde: NaiveDate, hasta: NaiveDate) -> f64 {
+        let unidades = |producto: &str, cantidad: f64| -> f64 {
+            let mut total = if producto == nombre { cantidad } else { 0.0 };
+            if let Some(kit) = self.productos.get(producto) {
+                total += kit
+                    .componentes
+                    .iter()
+                    .filter(|(componente, _)| componente == nombre)
+                    .map(|(_, por_kit)| por_kit * cantidad)
+                    .sum::<f64>();
+            }
+            total
+        };
+        let vendidas: f64 = self
+            .ventas
+            .iter()
+            .filter(|v| (desde..hasta).contains(&v.fecha.date_naive()))
+            .flat_map(|v| v.lineas.iter())
+            .map(|l| unidades(&l.producto, l.cantidad))
+            .sum();
+        let devueltas: f64 = self
+            .devoluciones
+            .iter()
+            .filter(|d| (desde..hasta).contains(&d.fecha.date_naive()))
+            .flat_map(|d| d.lineas.iter())
+            .map(|l| unidades(&l.producto, l.cantidad))
+            .sum();
+        vendidas - devueltas
+    }
+
+    // Demanda de los últimos períodos completos, del más antiguo al más reciente
+    pub fn serie_demanda(&self, nombre: &str, periodo: Periodo, periodos: usize) -> Vec<f64> {
+        let mut fin = periodo.inicio(Local::now().date_naive());
+        let mut serie = Vec::new();
+        for _ in 0..periodos {
+            let inicio = periodo.anterior(fin);
+            serie.push(self.demanda_en(nombre, inicio, fin));
+            fin = inicio;
+        }
+        serie.reverse();
+        serie
+    }
+
+    // El mínimo propuesto cubre la demanda durante el tiempo de entrega más el stock de seguridad
+    pub fn pronosticar_producto(
+        &self,
+        nombre: &str,
+        metodo: MetodoPronostico,
+        periodo: Periodo,
+        periodos: usize,
+        nivel_servicio: f64,
+    ) -> Option<Pronostico> {
+        let producto = self.productos.get(nombre)?;
+        let serie = self.serie_demanda(nombre, periodo, periodos);
+        let demanda = metodo.pronosticar(&serie)?;
+        let desviacion = desviacion_estandar(&serie);
+        let entrega = self
+            .reorden
+            .get(nombre)
+            .map_or(periodo.dias(), |r| r.tiempo_entrega as f64)
+            / periodo.dias();
+        let stock_seguridad = factor_servicio(nivel_servicio) * desviacion * entrega.sqrt();
+        Some(Pronostico {
+            producto: nombre.to_string(),
+            demanda,
+            desviacion,
+            stock_seguridad,
+            minima_actual: producto.cantidad_minima,
+            minima_propuesta: (demanda * entrega + stock_seguridad).ceil(),
+        })
+    }
+
+    // Pronósticos de los productos con ventas, sin kits porque no tienen existencia propia
+    pub fn proponer_minimos(
+        &self,
+        metodo: MetodoPronostico,
+        periodo: Periodo,
+        periodos: usize,
+        nivel_servicio: f64,
+    ) -> Vec<Pronostico> {
+        let mut nombres: Vec<&String> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty())
+            .map(|p| &p.nombre)
+            .collect();
+        nombres.sort();
+        nombres
+            .into_iter()
+            .filter_map(|nombre| {
+                self.pronosticar_producto(nombre, metodo, periodo, periodos, nivel_servicio)
+            })
+            .filter(|p| p.demanda > 0.0 || p.desviacion > 0.0)
+            .collect()
+    }
+}
+
+fn pedir_periodo() -> Periodo {
+    println!("Período de la serie (d: día, s: semana, m: mes; deje vacío para semana):");
+    match leer_entrada().to_lowercase().as_str() {
+        "d" => Periodo::Dia,
+        "m" => Periodo::Mes,
+        _ => Periodo::Semana,
+    }
+}
+
+fn pedir_metodo() -> Option<MetodoPronostico> {
+    println!("Método de pronóstico:");
+    println!("1. Promedio móvil");
+    println!("2. Suavizado exponencial");
+    println!("3. Descomposición estacional");
+    let metodo = match leer_entrada().as_str() {
+        "1" => {
+            println!("Ingrese el número de períodos a promediar:");
+            MetodoPronostico::PromedioMovil(leer_entrada().parse().ok()?)
+        }
+        "2" => {
+            println!("Ingrese el factor alfa entre 0 y 1 (por ejemplo 0.3):");
+            MetodoPronostico::SuavizadoExponencial(leer_entrada().parse().ok()?)
+        }
+        "3" => {
+            println!("Ingrese la longitud de la temporada en períodos (por ejemplo 12 meses):");
+            MetodoPronostico::Estacional(leer_entrada().parse().ok()?)
+        }
+        _ => return None,
+    };
+    Some(metodo)
+}
+
+fn pedir_historial(metodo: MetodoPronostico) -> usize {
+    let sugerido = match metodo {
+        MetodoPronostico::Estacional(temporada) => temporada * 2,
+        MetodoPronostico::PromedioMovil(ventana) => ventana.max(12),
+        MetodoPronostico::SuavizadoExponencial(_) => 12,
+    };
+    println!(
+        "Ingrese cuántos períodos de historial usar (deje vacío para {}):",
+        sugerido
+    );
+    leer_entrada().parse().unwrap_or(sugerido)
+}
+
+fn pedir_nivel_servicio() -> f64 {
+    println!("Ingrese el nivel de servicio en porcentaje (deje vacío para 95):");
+    match leer_entrada().parse::<f64>() {
+        Ok(nivel) if (50.0..100.0).contains(&nivel) => nivel / 100.0,
+        _ => 0.95,
+    }
+}
+
+fn mostrar_pronostico(pronostico: &Pronostico, periodo: Periodo) {
+    println!(
+        "{} - demanda por {}: {:.2} (desviación {:.2}) - stock de seguridad: {:.2} - mínimo actual: {} - propuesto: {}",
+        pronostico.producto,
+        periodo.nombre(),
+        pronostico.demanda,
+        pronostico.desviacion,
+        pronostico.stock_seguridad,
+        pronostico.minima_actual,
+        pronostico.minima_propuesta
+    );
+}
+
+pub fn menu_pronosticos(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Pronóstico de Demanda");
+        println!("---------------------");
+        println!("1. Ver Historial de Demanda de un Producto");
+        println!("2. Pronosticar un Producto");
+        println!("3. Proponer Cantidades Mínimas");
+        println!("4. Volver al Menú Principal");
+
+        let opcion = leer_entrada();
+        match opcion.as_str() {
+            "1" => {
+                println!("Ingrese el nombre, SKU o código de barras del producto:");
+                let entrada = leer_entrada();
+                let Some(nombre) = gestor_productos.resolver_producto(&entrada) else {
+                    println!("Producto no encontrado");
+                    pausar();
+                    continue;
+                };
+                let periodo = pedir_periodo();
+                println!("Ingrese cuántos períodos mostrar (deje vacío para 12):");
+                let periodos = leer_entrada().parse().unwrap_or(12);
+                let serie = gestor_productos.serie_demanda(&nombre, periodo, periodos);
+                let mut inicio = periodo.inicio(Local::now().date_naive());
+                for _ in 0..periodos {
+                    inicio = periodo.anterior(inicio);
+                }
+                for demanda in serie {
+                    println!("{} - {}", inicio, demanda);
+                    inicio = periodo.siguiente(inicio);
+                }
+            }
+            "2" | "3" => {
+                let nombre = if opcion == "2" {
+                    println!("Ingrese el nombre, SKU o código de barras del producto:");
+                    let entrada = leer_entrada();
+                    match gestor_productos.resolver_producto(&entrada) {
+                        Some(nombre) => Some(nombre),
+                        None => {
+                            println!("Producto no encontrado");
+                            pausar();
+                            continue;
+                        }
+                    }
+                } else {
+                    None
+                };
+                let periodo = pedir_periodo();
+                let Some(metodo) = pedir_metodo() else {
+                    println!("Método no válido");
+                    pausar();
+                    continue;
+                };
+                let periodos = pedir_historial(metodo);
+                let nivel_servicio = pedir_nivel_servicio();
+                println!(
+                    "Pronóstico con {} y nivel de servicio {}%",
+                    metodo.descripcion(),
+                    nivel_servicio * 100.0
+                );
+
+                let pronosticos = match &nombre {
+                    Some(nombre) => gestor_productos
+                        .pronosticar_producto(nombre, metodo, periodo, periodos, nivel_servicio)
+                        .into_iter()
+                        .collect(),
+                    None => {
+                        gestor_productos.proponer_minimos(metodo, periodo, periodos, nivel_servicio)
+                    }
+                };
+                if pronosticos.is_empty() {
+                    println!("No hay historial suficiente para el método elegido");
+                    pausar();
+                    continue;
+                }
+                for pronostico in &pronosticos {
+                    mostrar_pronostico(pronostico, periodo);
+                }
+
+                println!("¿Desea aplicar las cantidades mínimas propuestas? (s/n):");
+                if leer_entrada().to_lowercase() == "s" {
+                    for pronostico in &pronosticos {
+                        if let Some(producto) =
+                            gestor_productos.productos.get_mut(&pronostico.producto)
+                        {
+                            producto.cantidad_minima = pronostico.minima_propuesta;
+                        }
+                    }
+                    println!("Cantidades mínimas actualizadas");
+                }
+            }
+            "4" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aproximado(valor: f64, esperado: f64, tolerancia: f64) {
+        assert!(
+            (valor - esperado).abs() < tolerancia,
+            "se esperaba {} y se obtuvo {}",
+            esperado,
+            valor
+        );
+    }
+
+    #[test]
+    fn promedio_movil_de_los_ultimos_periodos() {
+        assert_eq!(promedio_movil(&[1.0, 2.0, 3.0, 4.0, 5.0], 3), Some(4.0));
+        assert_eq!(promedio_movil(&[1.0, 2.0], 3), None);
+        assert_eq!(promedio_movil(&[1.0, 2.0], 0), None);
+    }
+
+    #[test]
+    fn suavizado_exponencial_parte_del_primer_valor() {
+        // 10 -> 0.5 * 20 + 0.5 * 10 = 15 -> 0.5 * 30 + 0.5 * 15 = 22.5
+        assert_eq!(suavizado_exponencial(&[10.0, 20.0, 30.0], 0.5), Some(22.5));
+        assert_eq!(suavizado_exponencial(&[10.0, 20.0], 1.5), None);
+        assert_eq!(suavizado_exponencial(&[], 0.5), None);
+    }
+
+    #[test]
+    fn descomposicion_estacional_sin_tendencia() {
+        // Índices 2/3 y 4/3, la serie desestacionalizada es constante en 15
+        let pronostico = descomposicion_estacional(&[10.0, 20.0, 10.0, 20.0], 2).unwrap();
+        aproximado(pronostico, 10.0, 1e-9);
+    }
+
+    #[test]
+    fn descomposicion_estacional_con_tendencia() {
+        // Desestacionalizada 15, 15, 18, 18: pendiente 1.2 y tendencia 19.5 en el período 4
+        let pronostico = descomposicion_estacional(&[10.0, 20.0, 12.0, 24.0], 2).unwrap();
+        aproximado(pronostico, 13.0, 1e-9);
+        assert_eq!(descomposicion_estacional(&[1.0, 2.0, 3.0], 2), None);
+    }
+
+    #[test]
+    fn desviacion_estandar_muestral() {
+        assert_eq!(desviacion_estandar(&[2.0, 4.0, 6.0]), 2.0);
+        assert_eq!(desviacion_estandar(&[5.0]), 0.0);
+    }
+
+    #[test]
+    fn factor_servicio_de_la_normal() {
+        aproximado(factor_servicio(0.95), 1.645, 1e-3);
+        aproximado(factor_servicio(0.975), 1.960, 1e-3);
+        assert_eq!(factor_servicio(0.5), 0.0);
+    }
+}
@@ -0,0 +1,530 @@
+use crate::compras::LineaOrdenCompra;
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_numero, pedir_producto, GestorProductos};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub enum CantidadReorden {
+    Fija(f64),
+    // Cantidad económica de pedido a partir de la demanda anual, el costo de hacer un pedido
+    // y el costo anual de mantener una unidad como porcentaje de su costo
+    Eoq {
+        demanda_anual: f64,
+        costo_pedido: f64,
+        porcentaje_mantener: f64,
+    },
+}
+
+// Parámetros para decidir cuándo y cuánto volver a comprar de un producto
+#[derive(Serialize, Deserialize)]
+pub struct ParametrosReorden {
+    pub punto_reorden: f64,
+    pub cantidad: CantidadReorden,
+    pub proveedor: String,
+    pub tiempo_entrega: u32,
+}
+
+pub struct CompraSugerida {
+    pub producto: String,
+    pub disponible: f64,
+    pub en_pedido: f64,
+    pub cantidad: f64,
+    pub costo_unitario: f64,
+}
+
+// Fórmula de Wilson: raíz de 2 * demanda * costo de pedido / costo de mantener
+pub fn cantidad_economica(demanda_anual: f64, costo_pedido: f64, costo_mantener: f64) -> f64 {
+    if demanda_anual <= 0.0 || costo_pedido <= 0.0 || costo_mantener <= 0.0 {
+        return 0.0;
+    }
+    (2.0 * demanda_anual * costo_pedido / costo_mantener).sqrt()
+}
+
+// Implementación de métodos de reabastecimiento para GestorProductos
+impl GestorProductos {
+    pub fn definir_reorden(
+        &mut self,
+        nombre: &str,
+        parametros: ParametrosReorden,
+    ) -> Result<(), String> {
+        let producto = self
+            .productos
+            .get(nombre)
+            .ok_or_else(|| "Producto no encontrado".to_string())?;
+        if !producto.componentes.is_empty() {
+            return Err("Los kits se reabastecen a través de sus componentes".to_string());
+        }
+        if parametros.punto_reorden < 0.0 {
+            return Err("Punto de reorden no válido".to_string());
+        }
+        let valida = match &parametros.cantidad {
+            CantidadReorden::Fija(cantidad) => *cantidad > 0.0,
+            CantidadReorden::Eoq {
+                demanda_anual,
+                costo_pedido,
+                porcentaje_mantener,
+            } => *demanda_anual > 0.0 && *costo_pedido > 0.0 && *porcentaje_mantener > 0.0,
+        };
+        if !valida {
+            return Err("Cantidad de reorden no válida".to_string());
+        }
+        // El costo de mantener es un porcentaje del costo, sin costo la EOQ siempre daría cero
+        if matches!(parametros.cantidad, CantidadReorden::Eoq { .. }) && producto.costo <= 0.0 {
+            return Err(
+                "El producto no tiene costo, registre una compra o use una cantidad fija"
+                    .to_string(),
+            );
+        }
+        if parametros.proveedor.is_empty() {
+            return Err("Debe indicar el proveedor".to_string());
+        }
+        self.reorden.insert(nombre.to_string(), parametros);
+        Ok(())
+    }
+
+    pub fn cantidad_reorden(&self, nombre: &str) -> Option<f64> {
+        let parametros = self.reorden.get(nombre)?;
+        Some(match &parametros.cantidad {
+            CantidadReorden::Fija(cantidad) => *cantidad,
+            CantidadReorden::Eoq {
+                demanda_anual,
+                costo_pedido,
+                porcentaje_mantener,
+            } => {
+                let costo = self.productos.get(nombre).map_or(0.0, |p| p.costo);
+                cantidad_economica(
+                    *demanda_anual,
+                    *costo_pedido,
+                    costo * porcentaje_mantener / 100.0,
+                )
+                .ceil()
+            }
+        })
+    }
+
+    // Productos en o bajo su punto de reorden contando lo ya pedido, agrupados por proveedor
+    pub fn compras_sugeridas(&self) -> Vec<(String, Vec<CompraSugerida>)> {
+        let mut grupos: Vec<(String, Vec<CompraSugerida>)> = Vec::new();
+        let mut nombres: Vec<&String> = self.reorden.keys().collect();
+        nombres.sort();
+        for nombre in nombres {
+            let (Some(parametros), Some(producto)) =
+                (self.reorden.get(nombre), self.productos.get(nombre))
+            else {
+                continue;
+            };
+            let disponible = producto.cantidad_disponible;
+            let en_pedido = self.cantidad_en_pedido(nombre);
+            if disponible + en_pedido > parametros.punto_reorden {
+                continue;
+            }
+            // Si la cantidad de reorden no alcanza para superar el punto se pide lo que falta
+            let faltante = parametros.punto_reorden - disponible - en_pedido;
+            let cantidad = self
+                .cantidad_reorden(nombre)
+                .unwrap_or(0.0)
+                .max(faltante.ceil());
+            if cantidad <= 0.0 {
+                continue;
+            }
+            let sugerida = CompraSugerida {
+                producto: nombre.clone(),
+                disponible,
+                en_pedido,
+                cantidad,
+                costo_unitario: producto.costo,
+            };
+            match grupos.iter_mut().find(|(p, _)| *p == parametros.proveedor) {
+                Some((_, sugeridas)) => sugeridas.push(sugerida),
+                None => grupos.push((parametros.proveedor.clone(), vec![sugerida])),
+            }
+        }
+        grupos.sort_by(|a, b| a.0.cmp(&b.0));
+        grupos
+    }
+
+    // Convierte las compras sugeridas en una orden por proveedor, solo del indicado si se pasa
+    pub fn generar_ordenes_sugeridas(
+        &mut self,
+        proveedor: Option<&str>,
+        usuario: &str,
+    ) -> Result<Vec<u32>, String> {
+        let mut ordenes = Vec::new();
+        for (nombre, sugeridas) in self.compras_sugeridas() {
+            if proveedor.is_some_and(|p| !p.eq_ignore_ascii_case(&nombre)) {
+                continue;
+            }
+            let lineas = sugeridas
+                .into_iter()
+                .map(|s| LineaOrdenCompra {
+                    producto: s.producto,
+                    cantidad: s.cantidad,
+                    costo_unitario: s.costo_unitario,
+                })
+                .collect();
+            ordenes.push(self.crear_orden_compra(&nombre, lineas, usuario)?);
+        }
+        Ok(ordenes)
+    }
+}
+
+fn pedir_parametros(gestor_productos: &GestorProductos, nombre: &str) -> Option<ParametrosReorden> {
+    println!("Ingrese el proveedor:");
+    let proveedor = leer_entrada();
+    println!("Ingrese el tiempo de entrega del proveedor en días:");
+    let Ok(tiempo_entrega) = leer_entrada().parse::<u32>() else {
+        println!("El tiempo de entrega debe ser un número entero de días");
+        return None;
+    };
+
+    println!("¿Cantidad de reorden fija o calculada con EOQ? (f/e):");
+    let cantidad = if leer_entrada().to_lowercase() == "e" {
+        let demanda_anual = pedir_numero("Ingrese la demanda anual estimada en unidades:")?;
+        let costo_pedido = pedir_numero("Ingrese el costo de hacer un pedido:")?;
+        let porcentaje_mantener = pedir_numero(
+            "Ingrese el costo anual de mantener inventario como porcentaje del costo (por ejemplo 20):",
+        )?;
+        CantidadReorden::Eoq {
+            demanda_anual,
+            costo_pedido,
+            porcentaje_mantener,
+        }
+    } else {
+        CantidadReorden::Fija(pedir_numero("Ingrese la cantidad a pedir:")?)
+    };
+
+    // Sin punto de reorden explícito se usa la demanda durante el tiempo de entrega
+    let sugerido = match &cantidad {
+        CantidadReorden::Eoq { demanda_anual, .. } => {
+            Some((demanda_anual / 365.0 * tiempo_entrega as f64).ceil())
+        }
+        CantidadReorden::Fija(_) => None,
+    };
+    let minima = gestor_productos
+        .buscar_producto(nombre)
+        .map_or(0.0, |p| p.cantidad_minima);
+    let sugerido = sugerido.unwrap_or(minima);
+    println!(
+        "Ingrese el punto de reorden (deje vacío para {}):",
+        sugerido
+    );
+    let punto_reorden = match leer_entrada() {
+        punto if punto.is_empty() => sugerido,
+        punto => match punto.parse::<f64>() {
+            Ok(punto) => punto,
+            Err(_) => {
+                println!("Número no válido");
+                return None;
+            }
+        },
+    };
+
+    Some(ParametrosReorden {
+        punto_reorden,
+        cantidad,
+        proveedor,
+        tiempo_entrega,
+    })
+}
+
+pub fn menu_reabastecimiento(gestor_productos: &mut GestorProductos, usuario_actual: &str) {
+    loop {
+        limpiar_consola();
+        println!("Reabastecimiento");
+        println!("----------------");
+        println!("1. Definir Parámetros de Reorden");
+        println!("2. Ver Parámetros de Reorden");
+        println!("3. Compras Sugeridas");
+        println!("4. Generar Órdenes de Compra Sugeridas");
+        println!("5. Ver Órdenes de Compra");
+        println!("6. Recibir Orden de Compra");
+        println!("7. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                let Some(nombre) = pedir_producto(
+                    gestor_productos,
+                    "Ingrese el nombre, SKU o código de barras del producto:",
+                ) else {
+                    pausar();
+                    continue;
+                };
+                let Some(parametros) = pedir_parametros(gestor_productos, &nombre) else {
+                    pausar();
+                    continue;
+                };
+                match gestor_productos.definir_reorden(&nombre, parametros) {
+                    Ok(()) => println!(
+                        "Parámetros guardados, cantidad de reorden: {}",
+                        gestor_productos.cantidad_reorden(&nombre).unwrap_or(0.0)
+                    ),
+                    Err(err) => println!("Error al guardar los parámetros: {}", err),
+                }
+            }
+            "2" => {
+                if gestor_productos.reorden.is_empty() {
+                    println!("No hay productos con parámetros de reorden");
+                }
+                let mut nombres: Vec<&String> = gestor_productos.reorden.keys().collect();
+                nombres.sort();
+                for nombre in nombres {
+                    let parametros = &gestor_productos.reorden[nombre];
+                    println!(
+                        "{} - Proveedor: {} ({} días) - Punto de reorden: {} - Cantidad: {}{}",
+                        nombre,
+                        parametros.proveedor,
+                        parametros.tiempo_entrega,
+                        parametros.punto_reorden,
+                        gestor_productos.cantidad_reorden(nombre).unwrap_or(0.0),
+                        match parametros.cantidad {
+                            CantidadReorden::Eoq { .. } => " (EOQ)",
+                            CantidadReorden::Fija(_) => "",
+                        }
+                    );
+                }
+            }
+            "3" => {
+                let grupos = gestor_productos.compras_sugeridas();
+                if grupos.is_empty() {
+                    println!("No hay productos en su punto de reorden");
+                }
+                for (proveedor, sugeridas) in &grupos {
+                    println!("{}", proveedor);
+                    for sugerida in sugeridas {
+                        println!(
+                            "  {} - disponible: {} - en pedido: {} - pedir: {} (${:.2})",
+                            sugerida.producto,
+                            sugerida.disponible,
+                            sugerida.en_pedido,
+                            sugerida.cantidad,
+                            sugerida.cantidad * sugerida.costo_unitario
+                        );
+                    }
+                }
+            }
+            "4" => {
+                println!("Ingrese el proveedor (deje vacío para todos):");
+                let proveedor = leer_entrada();
+                let proveedor = (!proveedor.is_empty()).then_some(proveedor);
+                match gestor_productos
+                    .generar_ordenes_sugeridas(proveedor.as_deref(), usuario_actual)
+                {
+                    Ok(ordenes) if ordenes.is_empty() => println!("No hay compras sugeridas"),
+                    Ok(ordenes) => {
+                        for id in ordenes {
+                            if let Some(orden) =
+                                gestor_productos.ordenes_compra.iter().find(|o| o.id == id)
+                            {
+                                orden.mostrar();
+                            }
+                        }
+                    }
+                    Err(err) => println!("Error al generar las órdenes: {}", err),
+                }
+            }
+            "5" => {
+                if gestor_productos.ordenes_compra.is_empty() {
+                    println!("No hay órdenes de compra");
+                }
+                for orden in &gestor_productos.ordenes_compra {
+                    orden.mostrar();
+                }
+            }
+            "6" => {
+                println!("Ingrese el número de la orden de compra:");
+                let resultado = match leer_entrada().trim_start_matches('#').parse::<u32>() {
+                    Ok(id) => gestor_productos.recibir_orden_compra(id, usuario_actual),
+                    Err(_) => Err("Número no válido".to_string()),
+                };
+                match resultado {
+                    Ok(compras) => {
+                        println!("Orden recibida, se registraron {} compras", compras.len())
+                    }
+                    Err(err) => println!("Error al recibir la orden: {}", err),
+                }
+            }
+            "7" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Producto;
+
+    fn gestor_con_producto(nombre: &str, cantidad: f64, costo: f64) -> GestorProductos {
+        let mut gestor = GestorProductos::new();
+        agregar(&mut gestor, nombre, cantidad, costo);
+        gestor
+    }
+
+    fn agregar(gestor: &mut GestorProductos, nombre: &str, cantidad: f64, costo: f64) {
+        let mut producto = Producto::new(nombre.to_string(), String::new(), 20.0, cantidad, 0.0);
+        producto.costo = costo;
+        gestor.agregar_producto(nombre.to_string(), producto);
+    }
+
+    fn parametros(
+        punto_reorden: f64,
+        cantidad: CantidadReorden,
+        proveedor: &str,
+    ) -> ParametrosReorden {
+        ParametrosReorden {
+            punto_reorden,
+            cantidad,
+            proveedor: proveedor.to_string(),
+            tiempo_entrega: 7,
+        }
+    }
+
+    fn sugeridas(gestor: &GestorProductos) -> Vec<(String, String, f64)> {
+        gestor
+            .compras_sugeridas()
+            .into_iter()
+            .flat_map(|(proveedor, sugeridas)| {
+                sugeridas
+                    .into_iter()
+                    .map(move |s| (proveedor.clone(), s.producto, s.cantidad))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sugiere_solo_en_o_bajo_el_punto_de_reorden() {
+        let mut gestor = gestor_con_producto("Arroz", 11.0, 2.0);
+        gestor
+            .definir_reorden(
+                "Arroz",
+                parametros(10.0, CantidadReorden::Fija(25.0), "Granos SA"),
+            )
+            .unwrap();
+        assert!(sugeridas(&gestor).is_empty());
+
+        gestor
+            .productos
+            .get_mut("Arroz")
+            .unwrap()
+            .cantidad_disponible = 10.0;
+        assert_eq!(
+            sugeridas(&gestor),
+            vec![("Granos SA".to_string(), "Arroz".to_string(), 25.0)]
+        );
+    }
+
+    #[test]
+    fn lo_ya_pedido_cuenta_para_el_punto_de_reorden() {
+        let mut gestor = gestor_con_producto("Arroz", 4.0, 2.0);
+        gestor
+            .definir_reorden(
+                "Arroz",
+                parametros(10.0, CantidadReorden::Fija(25.0), "Granos SA"),
+            )
+            .unwrap();
+        gestor.generar_ordenes_sugeridas(None, "admin").unwrap();
+        assert_eq!(gestor.cantidad_en_pedido("Arroz"), 25.0);
+        assert!(sugeridas(&gestor).is_empty());
+    }
+
+    #[test]
+    fn cantidad_fija_menor_al_faltante_pide_el_faltante() {
+        let mut gestor = gestor_con_producto("Arroz", 0.0, 2.0);
+        gestor
+            .definir_reorden(
+                "Arroz",
+                parametros(30.0, CantidadReorden::Fija(5.0), "Granos SA"),
+            )
+            .unwrap();
+        assert_eq!(sugeridas(&gestor)[0].2, 30.0);
+    }
+
+    #[test]
+    fn cantidad_eoq_usa_el_costo_del_producto() {
+        // Mantener cuesta 10% de $10 = $1 por unidad, así la EOQ es 400
+        let mut gestor = gestor_con_producto("Arroz", 0.0, 10.0);
+        let eoq = CantidadReorden::Eoq {
+            demanda_anual: 1600.0,
+            costo_pedido: 50.0,
+            porcentaje_mantener: 10.0,
+        };
+        gestor
+            .definir_reorden("Arroz", parametros(10.0, eoq, "Granos SA"))
+            .unwrap();
+        assert_eq!(gestor.cantidad_reorden("Arroz"), Some(400.0));
+        assert_eq!(sugeridas(&gestor)[0].2, 400.0);
+    }
+
+    #[test]
+    fn rechaza_eoq_sin_costo() {
+        let mut gestor = gestor_con_producto("Arroz", 0.0, 0.0);
+        let eoq = CantidadReorden::Eoq {
+            demanda_anual: 1600.0,
+            costo_pedido: 50.0,
+            porcentaje_mantener: 10.0,
+        };
+        assert!(gestor
+            .definir_reorden("Arroz", parametros(10.0, eoq, "Granos SA"))
+            .is_err());
+        assert!(gestor
+            .definir_reorden(
+                "Arroz",
+                parametros(10.0, CantidadReorden::Fija(5.0), "Granos SA")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn agrupa_las_sugerencias_por_proveedor() {
+        let mut gestor = GestorProductos::new();
+        for (nombre, proveedor) in [
+            ("Arroz", "Granos SA"),
+            ("Leche", "Lácteos"),
+            ("Frijol", "Granos SA"),
+        ] {
+            agregar(&mut gestor, nombre, 0.0, 1.0);
+            gestor
+                .definir_reorden(
+                    nombre,
+                    parametros(5.0, CantidadReorden::Fija(10.0), proveedor),
+                )
+                .unwrap();
+        }
+        let grupos: Vec<(String, Vec<String>)> = gestor
+            .compras_sugeridas()
+            .into_iter()
+            .map(|(proveedor, sugeridas)| {
+                (
+                    proveedor,
+                    sugeridas.into_iter().map(|s| s.producto).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            grupos,
+            vec![
+                (
+                    "Granos SA".to_string(),
+                    vec!["Arroz".to_string(), "Frijol".to_string()]
+                ),
+                ("Lácteos".to_string(), vec!["Leche".to_string()]),
+            ]
+        );
+
+        let ordenes = gestor.generar_ordenes_sugeridas(Some("lácteos"), "admin");
+        assert_eq!(ordenes.map(|o| o.len()), Ok(1));
+        assert_eq!(gestor.cantidad_en_pedido("Leche"), 10.0);
+        assert_eq!(gestor.cantidad_en_pedido("Arroz"), 0.0);
+    }
+
+    #[test]
+    fn cantidad_economica_de_wilson() {
+        // Raíz de 2 * 1600 * 50 / 1 = 400
+        assert_eq!(cantidad_economica(1600.0, 50.0, 1.0), 400.0);
+        assert_eq!(cantidad_economica(1600.0, 50.0, 0.0), 0.0);
+    }
+}
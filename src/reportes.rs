@@ -0,0 +1,281 @@
+use crate::pronosticos::Periodo;
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, GestorProductos};
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy)]
+pub enum Agrupacion {
+    Periodo(Periodo),
+    Producto,
+    Categoria,
+    Usuario,
+}
+
+impl Agrupacion {
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Agrupacion::Periodo(Periodo::Dia) => "Día",
+            Agrupacion::Periodo(Periodo::Semana) => "Semana",
+            Agrupacion::Periodo(Periodo::Mes) => "Mes",
+            Agrupacion::Producto => "Producto",
+            Agrupacion::Categoria => "Categoría",
+            Agrupacion::Usuario => "Usuario",
+        }
+    }
+}
+
+// Unidades vendidas de una línea con su ingreso sin impuesto y su costo, negativas si es devolución
+pub struct MovimientoVenta {
+    pub fecha: DateTime<Local>,
+    pub producto: String,
+    pub usuario: String,
+    pub unidades: f64,
+    pub ingreso: f64,
+    pub costo: f64,
+}
+
+pub struct FilaReporte {
+    pub clave: String,
+    pub unidades: f64,
+    pub ingreso: f64,
+    pub costo: f64,
+}
+
+impl FilaReporte {
+    pub fn margen(&self) -> f64 {
+        self.ingreso - self.costo
+    }
+
+    pub fn porcentaje_margen(&self) -> f64 {
+        if self.ingreso.abs() > 0.0 {
+            self.margen() / self.ingreso * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+// Arma un CSV con el crate csv, así las comillas siguen las mismas reglas que al importar
+pub fn escribir_csv(encabezados: &[&str], filas: &[Vec<String>]) -> Result<String, String> {
+    let mut escritor = csv::Writer::from_writer(Vec::new());
+    escritor
+        .write_record(encabezados)
+        .map_err(|err| err.to_string())?;
+    for fila in filas {
+        escritor.write_record(fila).map_err(|err| err.to_string())?;
+    }
+    let bytes = escritor.into_inner().map_err(|err| err.to_string())?;
+    String::from_utf8(bytes).map_err(|err| err.to_string())
+}
+
+// Implementación de métodos de reportes de ventas para GestorProductos
+impl GestorProductos {
+    // Ventas y devoluciones del período, con el descuento de la venta prorrateado entre sus líneas
+    pub fn movimientos_venta(&self, desde: NaiveDate, hasta: NaiveDate) -> Vec<MovimientoVenta> {
+        let en_periodo = |fecha: &DateTime<Local>| (desde..=hasta).contains(&fecha.date_naive());
+        let mut movimientos = Vec::new();
+        for venta in self.ventas.iter().filter(|v| en_periodo(&v.fecha)) {
+            let suma: f64 = venta.lineas.iter().map(|l| l.subtotal()).sum();
+            let proporcion = if suma > 0.0 { venta.total / suma } else { 0.0 };
+            for linea in &venta.lineas {
+                let monto = linea.subtotal() * proporcion;
+                movimientos.push(MovimientoVenta {
+                    fecha: venta.fecha,
+                    producto: linea.producto.clone(),
+                    usuario: venta.usuario.clone(),
+                    unidades: linea.cantidad,
+                    ingreso: monto - self.configuracion.impuesto_incluido(monto),
+                    costo: linea.costo_unitario * linea.cantidad,
+                });
+            }
+        }
+        for devolucion in self.devoluciones.iter().filter(|d| en_periodo(&d.fecha)) {
+            let venta = self.buscar_venta(devolucion.venta);
+            for linea in &devolucion.lineas {
+                let costo_unitario = venta
+                    .and_then(|v| v.lineas.iter().find(|l| l.producto == linea.producto))
+                    .map_or(0.0, |l| l.costo_unitario);
+                let monto = linea.cantidad * linea.precio_unitario;
+                movimientos.push(MovimientoVenta {
+                    fecha: devolucion.fecha,
+                    producto: linea.producto.clone(),
+                    usuario: venta.map_or(devolucion.usuario.clone(), |v| v.usuario.clone()),
+                    unidades: -linea.cantidad,
+                    ingreso: -(monto - self.configuracion.impuesto_incluido(monto)),
+                    // La mercadería dada de baja no recupera su costo
+                    costo: if linea.reingresar {
+                        -costo_unitario * linea.cantidad
+                    } else {
+                        0.0
+                    },
+                });
+            }
+        }
+        movimientos
+    }
+
+    fn clave_reporte(&self, agrupacion: Agrupacion, movimiento: &MovimientoVenta) -> String {
+        match agrupacion {
+            Agrupacion::Periodo(periodo) => {
+                periodo.inicio(movimiento.fecha.date_naive()).to_string()
+            }
+            Agrupacion::Producto => movimiento.producto.clone(),
+            Agrupacion::Categoria => self
+                .productos
+                .get(&movimiento.producto)
+                .and_then(|p| p.categoria)
+                .map_or("Sin categoría".to_string(), |c| self.ruta_categoria(c)),
+            Agrupacion::Usuario => movimiento.usuario.clone(),
+        }
+    }
+
+    // Filas ordenadas por fecha si se agrupa por período, si no por ingreso de mayor a menor
+    pub fn reporte_ventas(
+        &self,
+        desde: NaiveDate,
+        hasta: NaiveDate,
+        agrupacion: Agrupacion,
+    ) -> Vec<FilaReporte> {
+        let mut filas: HashMap<String, FilaReporte> = HashMap::new();
+        for movimiento in self.movimientos_venta(desde, hasta) {
+            let clave = self.clave_reporte(agrupacion, &movimiento);
+            let fila = filas.entry(clave.clone()).or_insert(FilaReporte {
+                clave,
+                unidades: 0.0,
+                ingreso: 0.0,
+                costo: 0.0,
+            });
+            fila.unidades += movimiento.unidades;
+            fila.ingreso += movimiento.ingreso;
+            fila.costo += movimiento.costo;
+        }
+        let mut filas: Vec<FilaReporte> = filas.into_values().collect();
+        match agrupacion {
+            Agrupacion::Periodo(_) => filas.sort_by(|a, b| a.clave.cmp(&b.clave)),
+            _ => filas.sort_by(|a, b| b.ingreso.total_cmp(&a.ingreso)),
+        }
+        filas
+    }
+}
+
+pub fn reporte_csv(agrupacion: Agrupacion, filas: &[FilaReporte]) -> Result<String, String> {
+    let clave = agrupacion.nombre().to_lowercase();
+    let encabezados = [
+        clave.as_str(),
+        "unidades",
+        "ingreso",
+        "costo",
+        "margen",
+        "porcentaje_margen",
+    ];
+    let filas: Vec<Vec<String>> = filas
+        .iter()
+        .map(|fila| {
+            vec![
+                fila.clave.clone(),
+                fila.unidades.to_string(),
+                format!("{:.2}", fila.ingreso),
+                format!("{:.2}", fila.costo),
+                format!("{:.2}", fila.margen()),
+                format!("{:.2}", fila.porcentaje_margen()),
+            ]
+        })
+        .collect();
+    escribir_csv(&encabezados, &filas)
+}
+
+fn mostrar_reporte(agrupacion: Agrupacion, filas: &[FilaReporte]) {
+    println!(
+        "{:<30} {:>10} {:>12} {:>12} {:>12} {:>8}",
+        agrupacion.nombre(),
+        "Unidades",
+        "Ingreso",
+        "Costo",
+        "Margen",
+        "%"
+    );
+    for fila in filas {
+        println!(
+            "{:<30} {:>10} {:>12.2} {:>12.2} {:>12.2} {:>7.1}%",
+            fila.clave,
+            fila.unidades,
+            fila.ingreso,
+            fila.costo,
+            fila.margen(),
+            fila.porcentaje_margen()
+        );
+    }
+    let total = FilaReporte {
+        clave: "TOTAL".to_string(),
+        unidades: filas.iter().map(|f| f.unidades).sum(),
+        ingreso: filas.iter().map(|f| f.ingreso).sum(),
+        costo: filas.iter().map(|f| f.costo).sum(),
+    };
+    println!(
+        "{:<30} {:>10} {:>12.2} {:>12.2} {:>12.2} {:>7.1}%",
+        total.clave,
+        total.unidades,
+        total.ingreso,
+        total.costo,
+        total.margen(),
+        total.porcentaje_margen()
+    );
+}
+
+pub fn menu_reportes(gestor_productos: &GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Reportes de Ventas");
+        println!("------------------");
+        println!("Los ingresos se muestran sin impuesto y descontando devoluciones");
+        println!("1. Ventas por Día");
+        println!("2. Ventas por Semana");
+        println!("3. Ventas por Mes");
+        println!("4. Ventas por Producto");
+        println!("5. Ventas por Categoría");
+        println!("6. Ventas por Usuario");
+        println!("7. Volver al Menú Principal");
+
+        let agrupacion = match leer_entrada().as_str() {
+            "1" => Agrupacion::Periodo(Periodo::Dia),
+            "2" => Agrupacion::Periodo(Periodo::Semana),
+            "3" => Agrupacion::Periodo(Periodo::Mes),
+            "4" => Agrupacion::Producto,
+            "5" => Agrupacion::Categoria,
+            "6" => Agrupacion::Usuario,
+            "7" => break,
+            _ => {
+                println!("Opción no válida");
+                pausar();
+                continue;
+            }
+        };
+        let (Some(desde), Some(hasta)) = (
+            pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+            pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+        ) else {
+            pausar();
+            continue;
+        };
+
+        let filas = gestor_productos.reporte_ventas(desde, hasta, agrupacion);
+        if filas.is_empty() {
+            println!("No hay ventas en el período");
+            pausar();
+            continue;
+        }
+        mostrar_reporte(agrupacion, &filas);
+
+        println!("Ingrese un archivo para exportar a CSV (deje vacío para no exportar):");
+        let archivo = leer_entrada();
+        if !archivo.is_empty() {
+            let resultado = reporte_csv(agrupacion, &filas)
+                .and_then(|csv| std::fs::write(&archivo, csv).map_err(|err| err.to_string()));
+            match resultado {
+                Ok(()) => println!("Reporte exportado a {}", archivo),
+                Err(err) => println!("Error al exportar el reporte: {}", err),
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,681 @@
+use crate::pronosticos::Periodo;
+use crate::reportes::Agrupacion;
+use crate::{leer_entrada, limpiar_consola, pausar, pedir_fecha, GestorProductos};
+use chrono::Local;
+use printpdf::{
+    BuiltinFont, Color, ColorBits, ColorSpace, Image, ImageTransform, ImageXObject,
+    IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point, Px, Rgb,
+};
+use std::fs::File;
+use std::io::BufWriter;
+
+// Página A4 en milímetros
+const ANCHO_PAGINA: f32 = 210.0;
+const ALTO_PAGINA: f32 = 297.0;
+const MARGEN: f32 = 15.0;
+// El encabezado ocupa la parte superior y el pie de página la inferior
+const INICIO_CONTENIDO: f32 = 258.0;
+const FIN_CONTENIDO: f32 = 20.0;
+const ALTO_RENGLON: f32 = 6.0;
+const TAMANO_TEXTO: f32 = 9.0;
+const ALTO_LOGO: f32 = 18.0;
+
+pub struct Columna {
+    pub titulo: &'static str,
+    pub ancho: f32,
+    // Los números se alinean a la derecha
+    pub derecha: bool,
+}
+
+fn columna(titulo: &'static str, ancho: f32) -> Columna {
+    Columna {
+        titulo,
+        ancho,
+        derecha: false,
+    }
+}
+
+fn columna_numero(titulo: &'static str, ancho: f32) -> Columna {
+    Columna {
+        titulo,
+        ancho,
+        derecha: true,
+    }
+}
+
+enum Bloque {
+    Texto(String),
+    Subtitulo(String),
+    Tabla {
+        columnas: Vec<Columna>,
+        filas: Vec<Vec<String>>,
+    },
+}
+
+// Lo que ocupa un renglón de una página ya paginada
+enum Renglon<'a> {
+    Texto(&'a str, bool),
+    Fila(&'a [Columna], Vec<&'a str>, bool),
+    Espacio,
+}
+
+fn encabezado(columnas: &[Columna]) -> Renglon<'_> {
+    Renglon::Fila(columnas, columnas.iter().map(|c| c.titulo).collect(), true)
+}
+
+// Imagen en RGB de 8 bits lista para incrustar
+pub struct Logo {
+    ancho: u32,
+    alto: u32,
+    pixeles: Vec<u8>,
+}
+
+// Lee un PNG y lo convierte a RGB, mezclando la transparencia con fondo blanco
+pub fn cargar_logo(ruta: &str) -> Result<Logo, String> {
+    let archivo = File::open(ruta).map_err(|err| err.to_string())?;
+    let mut decoder = png::Decoder::new(std::io::BufReader::new(archivo));
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut lector = decoder.read_info().map_err(|err| err.to_string())?;
+    let mut datos = vec![
+        0;
+        lector
+            .output_buffer_size()
+            .ok_or("Imagen demasiado grande")?
+    ];
+    let info = lector
+        .next_frame(&mut datos)
+        .map_err(|err| err.to_string())?;
+    datos.truncate(info.buffer_size());
+
+    let sobre_blanco = |valor: u8, alfa: u8| {
+        ((valor as u32 * alfa as u32 + 255 * (255 - alfa as u32)) / 255) as u8
+    };
+    let pixeles = match info.color_type {
+        png::ColorType::Rgb => datos,
+        png::ColorType::Rgba => datos
+            .chunks(4)
+            .flat_map(|p| [p[0], p[1], p[2]].map(|v| sobre_blanco(v, p[3])))
+            .collect(),
+        png::ColorType::Grayscale => datos.iter().flat_map(|&v| [v, v, v]).collect(),
+        png::ColorType::GrayscaleAlpha => datos
+            .chunks(2)
+            .flat_map(|p| [sobre_blanco(p[0], p[1]); 3])
+            .collect(),
+        png::ColorType::Indexed => return Err("Formato de imagen no soportado".to_string()),
+    };
+    Ok(Logo {
+        ancho: info.width,
+        alto: info.height,
+        pixeles,
+    })
+}
+
+// Ancho aproximado del texto en Helvetica, suficiente para alinear y recortar columnas
+fn ancho_texto(texto: &str, tamano: f32) -> f32 {
+    texto.chars().count() as f32 * tamano * 0.55 * 0.3528
+}
+
+fn recortar(texto: &str, ancho: f32, tamano: f32) -> String {
+    if ancho_texto(texto, tamano) <= ancho {
+        return texto.to_string();
+    }
+    let maximo = (ancho / (tamano * 0.55 * 0.3528)) as usize;
+    let mut recortado: String = texto.chars().take(maximo.saturating_sub(1)).collect();
+    recortado.push('…');
+    recortado
+}
+
+fn linea_horizontal(capa: &PdfLayerReference, y: f32) {
+    capa.add_line(Line {
+        points: vec![
+            (Point::new(Mm(MARGEN), Mm(y)), false),
+            (Point::new(Mm(ANCHO_PAGINA - MARGEN), Mm(y)), false),
+        ],
+        is_closed: false,
+    });
+}
+
+pub fn dinero(monto: f64) -> String {
+    format!("${:.2}", monto)
+}
+
+// Precisión fija para que no aparezcan errores de coma flotante como 0.30000000000000004
+pub fn cantidad(valor: f64) -> String {
+    format!("{:.2}", valor)
+}
+
+// Documento de texto y tablas que se reparte en páginas con encabezado y pie
+pub struct DocumentoPdf {
+    titulo: String,
+    bloques: Vec<Bloque>,
+}
+
+impl DocumentoPdf {
+    pub fn new(titulo: &str) -> DocumentoPdf {
+        DocumentoPdf {
+            titulo: titulo.to_string(),
+            bloques: Vec::new(),
+        }
+    }
+
+    pub fn texto(&mut self, texto: String) {
+        self.bloques.push(Bloque::Texto(texto));
+    }
+
+    pub fn subtitulo(&mut self, texto: &str) {
+        self.bloques.push(Bloque::Subtitulo(texto.to_string()));
+    }
+
+    pub fn tabla(&mut self, columnas: Vec<Columna>, filas: Vec<Vec<String>>) {
+        self.bloques.push(Bloque::Tabla { columnas, filas });
+    }
+
+    // Las tablas que no caben continúan en la página siguiente repitiendo sus encabezados
+    fn paginar(&self) -> Vec<Vec<Renglon<'_>>> {
+        let por_pagina = ((INICIO_CONTENIDO - FIN_CONTENIDO) / ALTO_RENGLON) as usize;
+        let mut paginas: Vec<Vec<Renglon>> = vec![Vec::new()];
+        for bloque in &self.bloques {
+            let (renglones, tabla) = match bloque {
+                Bloque::Texto(texto) => (vec![Renglon::Texto(texto, false)], None),
+                Bloque::Subtitulo(texto) => {
+                    (vec![Renglon::Espacio, Renglon::Texto(texto, true)], None)
+                }
+                Bloque::Tabla { columnas, filas } => (
+                    filas
+                        .iter()
+                        .map(|fila| {
+                            Renglon::Fila(
+                                columnas,
+                                fila.iter().map(String::as_str).collect(),
+                                false,
+                            )
+                        })
+                        .collect(),
+                    Some(columnas.as_slice()),
+                ),
+            };
+            if let Some(columnas) = tabla {
+                // El encabezado no se deja solo al final de una página
+                if paginas.last().map_or(0, Vec::len) + 2 > por_pagina {
+                    paginas.push(Vec::new());
+                }
+                paginas.last_mut().unwrap().push(encabezado(columnas));
+            }
+            for renglon in renglones {
+                if paginas.last().map_or(0, Vec::len) >= por_pagina {
+                    paginas.push(tabla.map(encabezado).into_iter().collect());
+                }
+                paginas.last_mut().unwrap().push(renglon);
+            }
+        }
+        paginas
+    }
+
+    fn dibujar_renglon(
+        capa: &PdfLayerReference,
+        renglon: &Renglon,
+        y: f32,
+        normal: &IndirectFontRef,
+        negrita: &IndirectFontRef,
+    ) {
+        match renglon {
+            Renglon::Texto(texto, es_negrita) => {
+                let (fuente, tamano) = if *es_negrita {
+                    (negrita, TAMANO_TEXTO + 2.0)
+                } else {
+                    (normal, TAMANO_TEXTO)
+                };
+                capa.use_text(*texto, tamano, Mm(MARGEN), Mm(y), fuente);
+            }
+            Renglon::Fila(columnas, celdas, es_encabezado) => {
+                let fuente = if *es_encabezado { negrita } else { normal };
+                let mut x = MARGEN;
+                for (columna, celda) in columnas.iter().zip(celdas) {
+                    let texto = recortar(celda, columna.ancho - 2.0, TAMANO_TEXTO);
+                    let inicio = if columna.derecha {
+                        x + columna.ancho - 2.0 - ancho_texto(&texto, TAMANO_TEXTO)
+                    } else {
+                        x
+                    };
+                    capa.use_text(texto, TAMANO_TEXTO, Mm(inicio), Mm(y), fuente);
+                    x += columna.ancho;
+                }
+                if *es_encabezado {
+                    linea_horizontal(capa, y - 1.5);
+                }
+            }
+            Renglon::Espacio => {}
+        }
+    }
+
+    pub fn guardar(&self, empresa: &str, logo: Option<&Logo>, ruta: &str) -> Result<(), String> {
+        let (documento, primera, capa_inicial) = PdfDocument::new(
+            self.titulo.as_str(),
+            Mm(ANCHO_PAGINA),
+            Mm(ALTO_PAGINA),
+            "Contenido",
+        );
+        let normal = documento
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|err| err.to_string())?;
+        let negrita = documento
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|err| err.to_string())?;
+        let generado = Local::now().format("%Y-%m-%d %H:%M").to_string();
+
+        let paginas = self.paginar();
+        let total = paginas.len();
+        for (numero, renglones) in paginas.iter().enumerate() {
+            let capa = if numero == 0 {
+                documento.get_page(primera).get_layer(capa_inicial)
+            } else {
+                let (pagina, capa) =
+                    documento.add_page(Mm(ANCHO_PAGINA), Mm(ALTO_PAGINA), "Contenido");
+                documento.get_page(pagina).get_layer(capa)
+            };
+            capa.set_outline_color(Color::Rgb(Rgb::new(0.4, 0.4, 0.4, None)));
+            capa.set_outline_thickness(0.5);
+
+            // Encabezado con el logo a la izquierda y los datos de la empresa a su lado
+            let mut x_titulo = MARGEN;
+            if let Some(logo) = logo {
+                let dpi = logo.alto as f32 * 25.4 / ALTO_LOGO;
+                let imagen = Image::from(ImageXObject {
+                    width: Px(logo.ancho as usize),
+                    height: Px(logo.alto as usize),
+                    color_space: ColorSpace::Rgb,
+                    bits_per_component: ColorBits::Bit8,
+                    interpolate: true,
+                    image_data: logo.pixeles.clone(),
+                    image_filter: None,
+                    smask: None,
+                    clipping_bbox: None,
+                });
+                imagen.add_to_layer(
+                    capa.clone(),
+                    ImageTransform {
+                        translate_x: Some(Mm(MARGEN)),
+                        translate_y: Some(Mm(ALTO_PAGINA - 10.0 - ALTO_LOGO)),
+                        dpi: Some(dpi),
+                        ..Default::default()
+                    },
+                );
+                x_titulo += logo.ancho as f32 * 25.4 / dpi + 5.0;
+            }
+            capa.use_text(
+                empresa,
+                16.0,
+                Mm(x_titulo),
+                Mm(ALTO_PAGINA - 17.0),
+                &negrita,
+            );
+            capa.use_text(
+                self.titulo.as_str(),
+                12.0,
+                Mm(x_titulo),
+                Mm(ALTO_PAGINA - 24.0),
+                &normal,
+            );
+            let fecha = format!("Generado: {}", generado);
+            capa.use_text(
+                fecha.as_str(),
+                TAMANO_TEXTO,
+                Mm(ANCHO_PAGINA - MARGEN - ancho_texto(&fecha, TAMANO_TEXTO)),
+                Mm(ALTO_PAGINA - 24.0),
+                &normal,
+            );
+            linea_horizontal(&capa, ALTO_PAGINA - 10.0 - ALTO_LOGO - 3.0);
+
+            let mut y = INICIO_CONTENIDO;
+            for renglon in renglones {
+                DocumentoPdf::dibujar_renglon(&capa, renglon, y, &normal, &negrita);
+                y -= ALTO_RENGLON;
+            }
+
+            let pie = format!("Página {} de {}", numero + 1, total);
+            capa.use_text(
+                pie.as_str(),
+                TAMANO_TEXTO,
+                Mm((ANCHO_PAGINA - ancho_texto(&pie, TAMANO_TEXTO)) / 2.0),
+                Mm(10.0),
+                &normal,
+            );
+        }
+
+        let archivo = File::create(ruta).map_err(|err| err.to_string())?;
+        documento
+            .save(&mut BufWriter::new(archivo))
+            .map_err(|err| err.to_string())
+    }
+}
+
+// Implementación de métodos de reportes en PDF para GestorProductos
+impl GestorProductos {
+    // Valoración al costo promedio, a la fecha indicada o actual
+    pub fn pdf_valoracion(&self, fecha: Option<chrono::NaiveDate>) -> DocumentoPdf {
+        let mut documento = DocumentoPdf::new("Valoración de Inventario");
+        let existencias = match fecha {
+            Some(fecha) => {
+                documento.texto(format!("Existencias al cierre del {}", fecha));
+                self.inventario_al_cierre(fecha)
+            }
+            None => {
+                documento.texto("Existencias actuales".to_string());
+                self.inventario_en(Local::now())
+            }
+        };
+        let total: f64 = existencias.iter().map(|e| e.valor()).sum();
+        documento.tabla(
+            vec![
+                columna("Producto", 85.0),
+                columna_numero("Cantidad", 30.0),
+                columna_numero("Costo", 30.0),
+                columna_numero("Valor", 35.0),
+            ],
+            existencias
+                .iter()
+                .map(|e| {
+                    vec![
+                        e.producto.clone(),
+                        cantidad(e.cantidad),
+                        dinero(e.costo),
+                        dinero(e.valor()),
+                    ]
+                })
+                .collect(),
+        );
+        documento.subtitulo(&format!("Valor total del inventario: {}", dinero(total)));
+        documento
+    }
+
+    pub fn pdf_existencia_baja(&self) -> DocumentoPdf {
+        let mut documento = DocumentoPdf::new("Productos con Existencia Baja");
+        let mut productos: Vec<_> = self
+            .productos
+            .values()
+            .filter(|p| p.componentes.is_empty() && self.verificar_cantidad_minima(&p.nombre))
+            .collect();
+        productos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+        if productos.is_empty() {
+            documento.texto("Todos los productos están sobre su cantidad mínima".to_string());
+        }
+        documento.tabla(
+            vec![
+                columna("Producto", 70.0),
+                columna("SKU", 30.0),
+                columna_numero("Existencia", 20.0),
+                columna_numero("Mínimo", 20.0),
+                columna_numero("Faltante", 20.0),
+                columna_numero("En pedido", 20.0),
+            ],
+            productos
+                .iter()
+                .map(|p| {
+                    vec![
+                        p.nombre.clone(),
+                        p.sku.clone(),
+                        cantidad(p.cantidad_disponible),
+                        cantidad(p.cantidad_minima),
+                        cantidad(p.cantidad_minima - p.cantidad_disponible),
+                        cantidad(self.cantidad_en_pedido(&p.nombre)),
+                    ]
+                })
+                .collect(),
+        );
+        documento
+    }
+
+    pub fn pdf_resumen_ventas(
+        &self,
+        desde: chrono::NaiveDate,
+        hasta: chrono::NaiveDate,
+        agrupacion: Agrupacion,
+    ) -> DocumentoPdf {
+        let mut documento = DocumentoPdf::new("Resumen de Ventas");
+        documento.texto(format!(
+            "Del {} al {} por {}, ingresos sin impuesto y descontando devoluciones",
+            desde,
+            hasta,
+            agrupacion.nombre().to_lowercase()
+        ));
+        let filas = self.reporte_ventas(desde, hasta, agrupacion);
+        let ingreso: f64 = filas.iter().map(|f| f.ingreso).sum();
+        let costo: f64 = filas.iter().map(|f| f.costo).sum();
+        documento.tabla(
+            vec![
+                columna(agrupacion.nombre(), 60.0),
+                columna_numero("Unidades", 20.0),
+                columna_numero("Ingreso", 27.0),
+                columna_numero("Costo", 27.0),
+                columna_numero("Margen", 27.0),
+                columna_numero("%", 19.0),
+            ],
+            filas
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.clave.clone(),
+                        cantidad(f.unidades),
+                        dinero(f.ingreso),
+                        dinero(f.costo),
+                        dinero(f.margen()),
+                        format!("{:.1}%", f.porcentaje_margen()),
+                    ]
+                })
+                .collect(),
+        );
+        documento.subtitulo(&format!(
+            "Ingreso: {} - Costo: {} - Margen: {}",
+            dinero(ingreso),
+            dinero(costo),
+            dinero(ingreso - costo)
+        ));
+        documento
+    }
+
+    pub fn pdf_recibo(&self, id: u32) -> Result<DocumentoPdf, String> {
+        let venta = self.buscar_venta(id).ok_or("Venta no encontrada")?;
+        let mut documento = DocumentoPdf::new(&format!("Recibo de Venta #{}", venta.id));
+        documento.texto(format!("Fecha: {}", venta.fecha.format("%Y-%m-%d %H:%M")));
+        documento.texto(format!("Atendido por: {}", venta.usuario));
+        if let Some(cliente) = &venta.cliente {
+            documento.texto(format!("Cliente: {}", cliente));
+        }
+        documento.tabla(
+            vec![
+                columna("Producto", 75.0),
+                columna_numero("Cantidad", 20.0),
+                columna_numero("Precio", 28.0),
+                columna_numero("Descuento", 28.0),
+                columna_numero("Subtotal", 29.0),
+            ],
+            venta
+                .lineas
+                .iter()
+                .map(|l| {
+                    vec![
+                        l.producto.clone(),
+                        cantidad(l.cantidad),
+                        dinero(l.precio_unitario),
+                        dinero(l.descuento_promocion() + l.descuento_manual()),
+                        dinero(l.subtotal()),
+                    ]
+                })
+                .collect(),
+        );
+        if let Some(descuento) = venta.descuento {
+            let suma: f64 = venta.lineas.iter().map(|l| l.subtotal()).sum();
+            documento.texto(format!("Subtotal: {}", dinero(suma)));
+            documento.texto(format!(
+                "Descuento {}: -{}",
+                descuento.descripcion(),
+                dinero(descuento.monto(suma))
+            ));
+        }
+        documento.subtitulo(&format!("TOTAL: {}", dinero(venta.total)));
+        documento.texto(format!(
+            "Impuesto incluido: {}",
+            dinero(self.configuracion.impuesto_incluido(venta.total))
+        ));
+        for pago in &venta.pagos {
+            documento.texto(format!("{}: {}", pago.metodo.nombre(), dinero(pago.monto)));
+        }
+        Ok(documento)
+    }
+
+    pub fn pdf_orden_compra(&self, id: u32) -> Result<DocumentoPdf, String> {
+        let orden = self
+            .ordenes_compra
+            .iter()
+            .find(|o| o.id == id)
+            .ok_or("Orden de compra no encontrada")?;
+        let mut documento = DocumentoPdf::new(&format!("Orden de Compra #{}", orden.id));
+        documento.texto(format!("Proveedor: {}", orden.proveedor));
+        documento.texto(format!("Fecha: {}", orden.fecha.format("%Y-%m-%d")));
+        documento.texto(format!("Creada por: {}", orden.usuario));
+        documento.texto(match orden.recibida {
+            Some(fecha) => format!("Estado: recibida el {}", fecha.format("%Y-%m-%d")),
+            None => "Estado: pendiente".to_string(),
+        });
+        documento.tabla(
+            vec![
+                columna("Producto", 70.0),
+                columna("SKU", 35.0),
+                columna_numero("Cantidad", 20.0),
+                columna_numero("Costo", 27.0),
+                columna_numero("Subtotal", 28.0),
+            ],
+            orden
+                .lineas
+                .iter()
+                .map(|l| {
+                    vec![
+                        l.producto.clone(),
+                        self.productos
+                            .get(&l.producto)
+                            .map_or(String::new(), |p| p.sku.clone()),
+                        cantidad(l.cantidad),
+                        dinero(l.costo_unitario),
+                        dinero(l.cantidad * l.costo_unitario),
+                    ]
+                })
+                .collect(),
+        );
+        documento.subtitulo(&format!("Total: {}", dinero(orden.total())));
+        Ok(documento)
+    }
+
+    // Usa el nombre y el logo de la configuración; si el logo no se puede leer se omite
+    pub fn guardar_pdf(&self, documento: &DocumentoPdf, ruta: &str) -> Result<(), String> {
+        let logo = match &self.configuracion.logo {
+            Some(ruta_logo) => match cargar_logo(ruta_logo) {
+                Ok(logo) => Some(logo),
+                Err(err) => {
+                    println!("No se pudo cargar el logo: {}", err);
+                    None
+                }
+            },
+            None => None,
+        };
+        documento.guardar(&self.configuracion.nombre_empresa, logo.as_ref(), ruta)
+    }
+}
+
+fn pedir_id(mensaje: &str) -> Option<u32> {
+    println!("{}", mensaje);
+    let id = leer_entrada().parse::<u32>().ok();
+    if id.is_none() {
+        println!("Número no válido");
+    }
+    id
+}
+
+pub fn menu_reportes_pdf(gestor_productos: &GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Reportes en PDF");
+        println!("---------------");
+        println!("1. Valoración de Inventario");
+        println!("2. Productos con Existencia Baja");
+        println!("3. Resumen de Ventas");
+        println!("4. Recibo de Venta");
+        println!("5. Orden de Compra");
+        println!("6. Volver al Menú Principal");
+
+        let documento = match leer_entrada().as_str() {
+            "1" => {
+                println!(
+                    "Ingrese la fecha (AAAA-MM-DD, deje vacío para las existencias actuales):"
+                );
+                let entrada = leer_entrada();
+                if entrada.is_empty() {
+                    Ok(gestor_productos.pdf_valoracion(None))
+                } else {
+                    match chrono::NaiveDate::parse_from_str(&entrada, "%Y-%m-%d") {
+                        Ok(fecha) => Ok(gestor_productos.pdf_valoracion(Some(fecha))),
+                        Err(_) => Err("Fecha no válida".to_string()),
+                    }
+                }
+            }
+            "2" => Ok(gestor_productos.pdf_existencia_baja()),
+            "3" => {
+                let (Some(desde), Some(hasta)) = (
+                    pedir_fecha("Ingrese la fecha inicial (AAAA-MM-DD):"),
+                    pedir_fecha("Ingrese la fecha final (AAAA-MM-DD):"),
+                ) else {
+                    pausar();
+                    continue;
+                };
+                println!(
+                    "Agrupar por: 1. Día  2. Semana  3. Mes  4. Producto  5. Categoría  6. Usuario"
+                );
+                let agrupacion = match leer_entrada().as_str() {
+                    "1" => Agrupacion::Periodo(Periodo::Dia),
+                    "2" => Agrupacion::Periodo(Periodo::Semana),
+                    "3" => Agrupacion::Periodo(Periodo::Mes),
+                    "5" => Agrupacion::Categoria,
+                    "6" => Agrupacion::Usuario,
+                    _ => Agrupacion::Producto,
+                };
+                Ok(gestor_productos.pdf_resumen_ventas(desde, hasta, agrupacion))
+            }
+            "4" => match pedir_id("Ingrese el número de venta:") {
+                Some(id) => gestor_productos.pdf_recibo(id),
+                None => {
+                    pausar();
+                    continue;
+                }
+            },
+            "5" => match pedir_id("Ingrese el número de la orden de compra:") {
+                Some(id) => gestor_productos.pdf_orden_compra(id),
+                None => {
+                    pausar();
+                    continue;
+                }
+            },
+            "6" => break,
+            _ => {
+                println!("Opción no válida");
+                pausar();
+                continue;
+            }
+        };
+
+        match documento {
+            Ok(documento) => {
+                println!("Ingrese el archivo de destino (.pdf):");
+                let archivo = leer_entrada();
+                if archivo.is_empty() {
+                    println!("No se generó el reporte");
+                } else {
+                    match gestor_productos.guardar_pdf(&documento, &archivo) {
+                        Ok(()) => println!("Reporte guardado en {}", archivo),
+                        Err(err) => println!("Error al generar el PDF: {}", err),
+                    }
+                }
+            }
+            Err(err) => println!("Error: {}", err),
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,298 @@
+use crate::categorias::pedir_categoria;
+use crate::{generar_sku, leer_entrada, limpiar_consola, pausar, GestorProductos, Producto};
+use serde::{Deserialize, Serialize};
+
+// Producto que agrupa variantes (por ejemplo talla y color), el inventario se lleva en cada variante
+#[derive(Serialize, Deserialize)]
+pub struct ProductoPadre {
+    pub nombre: String,
+    pub descripcion: String,
+    pub precio: f64,
+    pub categoria: Option<u32>,
+    pub sku: String,
+    pub cantidad_minima: f64,
+    pub atributos: Vec<(String, Vec<String>)>,
+}
+
+pub struct ResumenVariantes {
+    pub variantes: usize,
+    pub disponible: f64,
+    pub vendido: f64,
+}
+
+// Implementación de métodos de variantes para GestorProductos
+impl GestorProductos {
+    pub fn crear_producto_con_variantes(&mut self, padre: ProductoPadre) -> Result<usize, String> {
+        if padre.nombre.is_empty() {
+            return Err("Nombre de producto no válido".to_string());
+        }
+        if self.productos_padre.contains_key(&padre.nombre)
+            || self.productos.contains_key(&padre.nombre)
+        {
+            return Err("El producto ya existe".to_string());
+        }
+        if padre.atributos.is_empty()
+            || padre
+                .atributos
+                .iter()
+                .any(|(atributo, valores)| atributo.is_empty() || valores.is_empty())
+        {
+            return Err("Cada atributo debe tener al menos un valor".to_string());
+        }
+        if self.productos_padre.values().any(|p| p.sku == padre.sku) {
+            return Err("El SKU ya está en uso".to_string());
+        }
+
+        let nombre = padre.nombre.clone();
+        self.productos_padre.insert(nombre.clone(), padre);
+        Ok(self.generar_variantes(&nombre))
+    }
+
+    // Agrega un nuevo valor a un atributo y crea las variantes que faltan
+    pub fn agregar_valor_atributo(
+        &mut self,
+        nombre_padre: &str,
+        atributo: &str,
+        valor: &str,
+    ) -> Result<usize, String> {
+        let padre = self
+            .productos_padre
+            .get_mut(nombre_padre)
+            .ok_or_else(|| "Producto no encontrado".to_string())?;
+        let valores = padre
+            .atributos
+            .iter_mut()
+            .find(|(nombre, _)| nombre.eq_ignore_ascii_case(atributo))
+            .map(|(_, valores)| valores)
+            .ok_or_else(|| "Atributo no encontrado".to_string())?;
+        if valor.is_empty() || valores.iter().any(|v| v.eq_ignore_ascii_case(valor)) {
+            return Err("Valor no válido o repetido".to_string());
+        }
+        valores.push(valor.to_string());
+        Ok(self.generar_variantes(nombre_padre))
+    }
+
+    // Crea una variante por cada combinación de valores que todavía no exista
+    fn generar_variantes(&mut self, nombre_padre: &str) -> usize {
+        let padre = &self.productos_padre[nombre_padre];
+        let mut combinaciones: Vec<Vec<(String, String)>> = vec![Vec::new()];
+        for (atributo, valores) in &padre.atributos {
+            combinaciones = combinaciones
+                .into_iter()
+                .flat_map(|combinacion| {
+                    valores.iter().map(move |valor| {
+                        let mut combinacion = combinacion.clone();
+                        combinacion.push((atributo.clone(), valor.clone()));
+                        combinacion
+                    })
+                })
+                .collect();
+        }
+
+        let mut nuevas = Vec::new();
+        for atributos in combinaciones {
+            let valores: Vec<&str> = atributos.iter().map(|(_, v)| v.as_str()).collect();
+            let nombre = format!("{} ({})", padre.nombre, valores.join(", "));
+            if self.productos.contains_key(&nombre) {
+                continue;
+            }
+            let mut variante = Producto::new(
+                nombre.clone(),
+                padre.descripcion.clone(),
+                padre.precio,
+                0.0,
+                padre.cantidad_minima,
+            );
+            variante.sku = format!(
+                "{}-{}",
+                padre.sku,
+                valores
+                    .iter()
+                    .map(|v| generar_sku(v))
+                    .collect::<Vec<String>>()
+                    .join("-")
+            );
+            variante.categoria = padre.categoria;
+            variante.padre = Some(padre.nombre.clone());
+            variante.atributos = atributos;
+            nuevas.push((nombre, variante));
+        }
+
+        let creadas = nuevas.len();
+        for (nombre, variante) in nuevas {
+            self.agregar_producto(nombre, variante);
+        }
+        creadas
+    }
+
+    pub fn variantes(&self, nombre_padre: &str) -> Vec<&Producto> {
+        let mut variantes: Vec<&Producto> = self
+            .productos
+            .values()
+            .filter(|p| p.padre.as_deref() == Some(nombre_padre))
+            .collect();
+        variantes.sort_by(|a, b| a.sku.cmp(&b.sku));
+        variantes
+    }
+
+    pub fn resumen_variantes(&self, nombre_padre: &str) -> Option<ResumenVariantes> {
+        self.productos_padre.get(nombre_padre)?;
+        let variantes = self.variantes(nombre_padre);
+        Some(ResumenVariantes {
+            variantes: variantes.len(),
+            disponible: variantes.iter().map(|v| v.cantidad_disponible).sum(),
+            vendido: variantes.iter().map(|v| v.vendido).sum(),
+        })
+    }
+}
+
+pub fn menu_variantes(gestor_productos: &mut GestorProductos) {
+    loop {
+        limpiar_consola();
+        println!("Productos con Variantes");
+        println!("-----------------------");
+        println!("1. Crear Producto con Variantes");
+        println!("2. Ver Variantes de un Producto");
+        println!("3. Agregar Valor a un Atributo");
+        println!("4. Listar Productos con Variantes");
+        println!("5. Volver al Menú Principal");
+
+        match leer_entrada().as_str() {
+            "1" => {
+                println!("Ingrese el nombre del producto:");
+                let nombre = leer_entrada();
+
+                println!("Ingrese la descripción del producto:");
+                let descripcion = leer_entrada();
+
+                println!("Ingrese el precio del producto:");
+                let precio: f64 = match leer_entrada().parse() {
+                    Ok(precio) => precio,
+                    Err(_) => {
+                        println!("Precio no válido");
+                        pausar();
+                        continue;
+                    }
+                };
+
+                println!("Ingrese la cantidad mínima de cada variante:");
+                let cantidad_minima: f64 = match leer_entrada().parse() {
+                    Ok(cantidad) => cantidad,
+                    Err(_) => {
+                        println!("Cantidad mínima no válida");
+                        pausar();
+                        continue;
+                    }
+                };
+
+                println!("¿Desea asignar una categoría? (s/n):");
+                let categoria = if leer_entrada().to_lowercase() == "s" {
+                    pedir_categoria(gestor_productos)
+                } else {
+                    None
+                };
+
+                println!("Ingrese el SKU base (deje vacío para generarlo):");
+                let sku = match leer_entrada() {
+                    sku if sku.is_empty() => generar_sku(&nombre),
+                    sku => generar_sku(&sku),
+                };
+
+                println!("Ingrese los atributos uno por línea con el formato");
+                println!("Atributo: valor1, valor2 (por ejemplo Talla: S, M, L)");
+                println!("Deje una línea vacía para terminar:");
+                let mut atributos = Vec::new();
+                loop {
+                    let linea = leer_entrada();
+                    if linea.is_empty() {
+                        break;
+                    }
+                    match linea.split_once(':') {
+                        Some((atributo, valores)) => atributos.push((
+                            atributo.trim().to_string(),
+                            valores
+                                .split(',')
+                                .map(|v| v.trim().to_string())
+                                .filter(|v| !v.is_empty())
+                                .collect(),
+                        )),
+                        None => println!("Formato no válido, se ignora la línea"),
+                    }
+                }
+
+                let padre = ProductoPadre {
+                    nombre,
+                    descripcion,
+                    precio,
+                    categoria,
+                    sku,
+                    cantidad_minima,
+                    atributos,
+                };
+                match gestor_productos.crear_producto_con_variantes(padre) {
+                    Ok(creadas) => println!("Se crearon {} variantes", creadas),
+                    Err(err) => println!("Error al crear el producto: {}", err),
+                }
+            }
+            "2" => {
+                println!("Ingrese el nombre del producto:");
+                let nombre = leer_entrada();
+                match gestor_productos.resumen_variantes(&nombre) {
+                    Some(resumen) => {
+                        for variante in gestor_productos.variantes(&nombre) {
+                            println!(
+                                "{} - {} - Disponible: {} - Vendido: {}",
+                                variante.sku,
+                                variante.nombre,
+                                variante.cantidad_disponible,
+                                variante.vendido
+                            );
+                        }
+                        println!("-----");
+                        println!("Variantes: {}", resumen.variantes);
+                        println!("Total disponible: {}", resumen.disponible);
+                        println!("Total vendido: {}", resumen.vendido);
+                    }
+                    None => println!("Producto no encontrado"),
+                }
+            }
+            "3" => {
+                println!("Ingrese el nombre del producto:");
+                let nombre = leer_entrada();
+                println!("Ingrese el atributo:");
+                let atributo = leer_entrada();
+                println!("Ingrese el nuevo valor:");
+                let valor = leer_entrada();
+                match gestor_productos.agregar_valor_atributo(&nombre, &atributo, &valor) {
+                    Ok(creadas) => println!("Se crearon {} variantes", creadas),
+                    Err(err) => println!("{}", err),
+                }
+            }
+            "4" => {
+                let mut nombres: Vec<&String> = gestor_productos.productos_padre.keys().collect();
+                nombres.sort();
+                if nombres.is_empty() {
+                    println!("No hay productos con variantes");
+                }
+                for nombre in nombres {
+                    let padre = &gestor_productos.productos_padre[nombre];
+                    let atributos: Vec<&str> =
+                        padre.atributos.iter().map(|(a, _)| a.as_str()).collect();
+                    println!(
+                        "{} - {} - {}",
+                        padre.sku,
+                        padre.nombre,
+                        atributos.join(", ")
+                    );
+                }
+            }
+            "5" => {
+                break;
+            }
+            _ => {
+                println!("Opción no válida");
+            }
+        }
+        pausar();
+    }
+}
@@ -0,0 +1,243 @@
+use crate::descuentos::Descuento;
+use crate::pagos::{validar_pagos, MetodoPago, Pago};
+use crate::GestorProductos;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LineaVenta {
+    pub producto: String,
+    pub cantidad: f64,
+    pub precio_unitario: f64,
+    // Costo al momento de la venta, para calcular el margen
+    pub costo_unitario: f64,
+    // Descuento manual, se aplica después de la promoción
+    pub descuento: Option<Descuento>,
+    // Nombre y monto de la promoción aplicada automáticamente
+    pub promocion: Option<(String, f64)>,
+}
+
+impl LineaVenta {
+    pub fn bruto(&self) -> f64 {
+        self.cantidad * self.precio_unitario
+    }
+
+    pub fn descuento_promocion(&self) -> f64 {
+        self.promocion.as_ref().map_or(0.0, |(_, monto)| *monto)
+    }
+
+    pub fn descuento_manual(&self) -> f64 {
+        let base = self.bruto() - self.descuento_promocion();
+        self.descuento.map_or(0.0, |d| d.monto(base))
+    }
+
+    pub fn subtotal(&self) -> f64 {
+        self.bruto() - self.descuento_promocion() - self.descuento_manual()
+    }
+}
+
+// Total de las líneas menos el descuento de la venta
+pub fn total_venta(lineas: &[LineaVenta], descuento: Option<Descuento>) -> f64 {
+    let suma: f64 = lineas.iter().map(LineaVenta::subtotal).sum();
+    suma - descuento.map_or(0.0, |d| d.monto(suma))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Venta {
+    pub id: u32,
+    pub fecha: DateTime<Local>,
+    pub usuario: String,
+    pub cliente: Option<String>,
+    pub lineas: Vec<LineaVenta>,
+    pub descuento: Option<Descuento>,
+    pub total: f64,
+    pub pagos: Vec<Pago>,
+    pub sesion: Option<u32>,
+}
+
+impl Venta {
+    pub fn mostrar_ticket(&self) {
+        println!("Chepe te Vende");
+        println!(
+            "Venta #{} - {}",
+            self.id,
+            self.fecha.format("%Y-%m-%d %H:%M")
+        );
+        println!("Atendido por: {}", self.usuario);
+        if let Some(cliente) = &self.cliente {
+            println!("Cliente: {}", cliente);
+        }
+        if let Some(sesion) = self.sesion {
+            println!("Caja: sesión #{}", sesion);
+        }
+        println!("------------------------------");
+        for linea in &self.lineas {
+            println!(
+                "{} x {} @ ${:.2} = ${:.2}",
+                linea.cantidad,
+                linea.producto,
+                linea.precio_unitario,
+                linea.bruto()
+            );
+            if let Some((nombre, monto)) = &linea.promocion {
+                println!("  {}: -${:.2}", nombre, monto);
+            }
+            if let Some(descuento) = linea.descuento {
+                println!(
+                    "  Descuento {}: -${:.2}",
+                    descuento.descripcion(),
+                    linea.descuento_manual()
+                );
+            }
+        }
+        println!("------------------------------");
+        if let Some(descuento) = self.descuento {
+            let suma: f64 = self.lineas.iter().map(LineaVenta::subtotal).sum();
+            println!("Subtotal: ${:.2}", suma);
+            println!(
+                "Descuento {}: -${:.2}",
+                descuento.descripcion(),
+                descuento.monto(suma)
+            );
+        }
+        println!("TOTAL: ${:.2}", self.total);
+        for pago in &self.pagos {
+            println!("{}: ${:.2}", pago.metodo.nombre(), pago.monto);
+        }
+    }
+}
+
+// Implementación de métodos de ventas para GestorProductos
+impl GestorProductos {
+    pub fn nueva_linea_venta(&self, nombre: &str, cantidad: f64) -> Result<LineaVenta, String> {
+        if cantidad <= 0.0 {
+            return Err("Cantidad no válida".to_string());
+        }
+        match self.precio_para(nombre, cantidad, None) {
+            Some(precio) => Ok(LineaVenta {
+                producto: nombre.to_string(),
+                cantidad,
+                precio_unitario: precio,
+                costo_unitario: self.costo_venta(nombre),
+                descuento: None,
+                promocion: None,
+            }),
+            None => Err("Producto no encontrado".to_string()),
+        }
+    }
+
+    // Los kits cuestan lo que cuestan sus componentes
+    pub fn costo_venta(&self, nombre: &str) -> f64 {
+        let Some(producto) = self.productos.get(nombre) else {
+            return 0.0;
+        };
+        if producto.componentes.is_empty() {
+            return producto.costo;
+        }
+        producto
+            .componentes
+            .iter()
+            .map(|(componente, cantidad)| {
+                self.productos.get(componente).map_or(0.0, |c| c.costo) * cantidad
+            })
+            .sum()
+    }
+
+    pub fn buscar_venta(&self, id: u32) -> Option<&Venta> {
+        self.ventas.iter().find(|v| v.id == id)
+    }
+
+    // Vende todas las líneas con vender_producto, si alguna falla se revierten las anteriores
+    pub fn registrar_venta(
+        &mut self,
+        mut lineas: Vec<LineaVenta>,
+        cliente: Option<&str>,
+        descuento: Option<Descuento>,
+        pagos: Vec<Pago>,
+        usuario: &str,
+        es_administrador: bool,
+    ) -> Result<u32, String> {
+        if lineas.is_empty() {
+            return Err("La venta no tiene productos".to_string());
+        }
+        if cliente.is_some_and(|c| !self.clientes.contains_key(c)) {
+            return Err("Cliente no encontrado".to_string());
+        }
+        self.resolver_precios(&mut lineas, cliente);
+        for linea in lineas.iter_mut() {
+            linea.costo_unitario = self.costo_venta(&linea.producto);
+        }
+        self.aplicar_promociones(&mut lineas);
+        self.validar_descuentos_venta(&lineas, descuento, es_administrador)?;
+        let total = total_venta(&lineas, descuento);
+        validar_pagos(total, &pagos)?;
+
+        let mut respaldo = Vec::new();
+        for linea in &lineas {
+            if let Some(producto) = self.productos.get(&linea.producto) {
+                respaldo.push(linea.producto.clone());
+                respaldo.extend(producto.componentes.iter().map(|(c, _)| c.clone()));
+            }
+        }
+        let respaldo: Vec<(String, f64, f64)> = respaldo
+            .into_iter()
+            .filter_map(|nombre| {
+                let producto = self.productos.get(&nombre)?;
+                Some((nombre, producto.cantidad_disponible, producto.vendido))
+            })
+            .collect();
+
+        let movimientos = self.movimientos.len();
+        for linea in &lineas {
+            if let Err(err) = self.vender_producto(&linea.producto, linea.cantidad) {
+                for (nombre, cantidad_disponible, vendido) in &respaldo {
+                    if let Some(producto) = self.productos.get_mut(nombre) {
+                        producto.cantidad_disponible = *cantidad_disponible;
+                        producto.vendido = *vendido;
+                    }
+                }
+                self.movimientos.truncate(movimientos);
+                return Err(format!("{}: {}", linea.producto, err));
+            }
+        }
+
+        let id = self.ventas.len() as u32 + 1;
+        let sesion = self.sesion_abierta(usuario);
+        self.ventas.push(Venta {
+            id,
+            fecha: Local::now(),
+            usuario: usuario.to_string(),
+            cliente: cliente.map(str::to_string),
+            lineas,
+            descuento,
+            total,
+            pagos,
+            sesion,
+        });
+        Ok(id)
+    }
+
+    // Venta sin descuento cobrada completa con un solo método de pago, para la línea de
+    // comandos y la API; el total se calcula igual que en registrar_venta
+    pub fn registrar_venta_un_pago(
+        &mut self,
+        mut lineas: Vec<LineaVenta>,
+        cliente: Option<&str>,
+        metodo: MetodoPago,
+        usuario: &str,
+    ) -> Result<u32, String> {
+        self.resolver_precios(&mut lineas, cliente);
+        self.aplicar_promociones(&mut lineas);
+        let total = total_venta(&lineas, None);
+        let pagos = if total > 0.0 {
+            vec![Pago {
+                metodo,
+                monto: total,
+            }]
+        } else {
+            Vec::new()
+        };
+        // Sin descuentos manuales el límite del rol no aplica
+        self.registrar_venta(lineas, cliente, None, pagos, usuario, false)
+    }
+}